@@ -10,6 +10,8 @@ use core::hint;
 #[cfg(not(test))]
 use core::ptr::{self, NonNull};
 
+#[cfg(kani)]
+pub(crate) mod model;
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,414 @@
+//! A model allocator for verification harnesses.
+//!
+//! [`ModelAlloc`] hands out memory from [`Global`], but may fail any allocation
+//! nondeterministically, checks that every block is deallocated with the layout
+//! it was allocated with, and keeps track of live blocks so that harnesses can
+//! assert that nothing leaked. Harnesses pass `&ModelAlloc` to the `*_in`
+//! constructors, which keeps the allocator `Copy` and lets the harness inspect
+//! it after the collection is gone:
+//!
+//! ```ignore (verification only)
+//! let model = ModelAlloc::new();
+//! let mut v = Vec::new_in(&model);
+//! v.push(1u8);
+//! drop(v);
+//! model.assert_no_leaks();
+//! ```
+//!
+//! [`Tracked`] elements play the same role for the values stored in a
+//! collection: each one counts its drops, so a harness can check that nothing
+//! was dropped twice or leaked, and find out where each element ended up.
+//!
+//! # Panics
+//!
+//! Kani does not model unwinding: a panic ends the execution it happens on,
+//! and no destructor runs after it. The drop guards that keep a collection
+//! sound when a closure or an element's destructor panics are therefore
+//! checked in two halves. A `should_panic` harness lets the real closure or
+//! destructor panic while the guard is armed, which shows that nothing up to
+//! the panic goes out of bounds or drops twice. The guard itself is checked
+//! by dropping it in the state a panic at that point leaves it in, which the
+//! harnesses reach without panicking, e.g. by leaving an iterator part-way.
+
+use core::cell::Cell;
+use core::ops::Range;
+use core::ptr::{self, NonNull};
+
+use safety::{ensures, requires};
+
+use super::{AllocError, Allocator, Global, GlobalAlloc, Layout};
+use crate::kani;
+
+/// Number of blocks a single `ModelAlloc` can keep live at once.
+const MAX_BLOCKS: usize = 4;
+
+/// An instrumented allocator for use in `#[kani::proof]` harnesses.
+pub(crate) struct ModelAlloc {
+    can_fail: bool,
+    blocks: [Cell<Option<(NonNull<u8>, Layout)>>; MAX_BLOCKS],
+    allocations: Cell<usize>,
+}
+
+impl ModelAlloc {
+    /// Creates an allocator whose allocations always succeed.
+    pub(crate) const fn new() -> Self {
+        ModelAlloc {
+            can_fail: false,
+            blocks: [const { Cell::new(None) }; MAX_BLOCKS],
+            allocations: Cell::new(0),
+        }
+    }
+
+    /// Creates an allocator where any allocation, including the one behind a
+    /// `grow` or `shrink`, may fail.
+    pub(crate) const fn fallible() -> Self {
+        ModelAlloc { can_fail: true, ..ModelAlloc::new() }
+    }
+
+    /// Returns the number of blocks that have been allocated and not yet
+    /// deallocated.
+    pub(crate) fn live(&self) -> usize {
+        self.blocks.iter().filter(|block| block.get().is_some()).count()
+    }
+
+    /// Returns the number of successful allocations so far.
+    pub(crate) fn allocations(&self) -> usize {
+        self.allocations.get()
+    }
+
+    /// Returns the layout of the live block starting at `ptr`, if any.
+    pub(crate) fn layout_of(&self, ptr: NonNull<u8>) -> Option<Layout> {
+        self.blocks.iter().find_map(|block| match block.get() {
+            Some((start, layout)) if start == ptr => Some(layout),
+            _ => None,
+        })
+    }
+
+    /// Returns `true` if `ptr` is a live block allocated with exactly `layout`.
+    pub(crate) fn owns(&self, ptr: NonNull<u8>, layout: Layout) -> bool {
+        self.blocks.iter().any(|block| block.get() == Some((ptr, layout)))
+    }
+
+    /// Asserts that every block allocated so far has been deallocated.
+    pub(crate) fn assert_no_leaks(&self) {
+        assert_eq!(self.live(), 0, "model allocator leaked a block");
+    }
+}
+
+#[unstable(feature = "allocator_api", issue = "32838")]
+unsafe impl Allocator for ModelAlloc {
+    #[ensures(|result: &Result<NonNull<[u8]>, AllocError>| result.map_or(true, |block| {
+        block.len() == layout.size() && self.owns(block.cast(), layout)
+    }))]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if self.can_fail && kani::any() {
+            return Err(AllocError);
+        }
+        let block = Global.allocate(layout)?;
+        let slot = self
+            .blocks
+            .iter()
+            .find(|slot| slot.get().is_none())
+            .expect("model allocator is out of block slots");
+        slot.set(Some((block.cast(), layout)));
+        self.allocations.set(self.allocations.get() + 1);
+        Ok(block)
+    }
+
+    #[requires(self.owns(ptr, layout))]
+    #[ensures(|_| self.live() == old(self.live()) - 1)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // Zero-sized blocks of the same alignment share their dangling address,
+        // so it is enough to release any one of the matching slots.
+        let slot = self.blocks.iter().find(|slot| slot.get() == Some((ptr, layout)));
+        let Some(slot) = slot else {
+            panic!("deallocated a block that is not live, or with the wrong layout");
+        };
+        slot.set(None);
+        // SAFETY: `ptr` was allocated by `Global` with `layout` and is still live.
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[unstable(feature = "allocator_api", issue = "32838")]
+unsafe impl GlobalAlloc for ModelAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocate(layout).map_or(ptr::null_mut(), |block| block.as_mut_ptr())
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the caller guarantees `ptr` came from `alloc`, which never
+        // returns null on success.
+        unsafe { self.deallocate(NonNull::new_unchecked(ptr), layout) }
+    }
+}
+
+/// Returns an arbitrary layout of at most `MAX_SIZE` bytes, aligned to at
+/// most 8.
+pub(crate) fn any_layout<const MAX_SIZE: usize>() -> Layout {
+    let size: usize = kani::any_where(|size| *size <= MAX_SIZE);
+    let align = 1 << kani::any_where(|shift: &u32| *shift <= 3);
+    Layout::from_size_align(size, align).unwrap()
+}
+
+/// A collection element that counts how many times it has been dropped.
+///
+/// Every element carries an id, which indexes into a table of drop counters
+/// owned by the harness.
+#[derive(Debug)]
+pub(crate) struct Tracked<'a> {
+    id: u8,
+    drops: &'a [Cell<u8>],
+}
+
+impl<'a> Tracked<'a> {
+    /// Creates an element whose drops are counted in `drops[id]`.
+    pub(crate) fn new(id: u8, drops: &'a [Cell<u8>]) -> Self {
+        Tracked { id, drops }
+    }
+
+    /// Returns the id this element was created with.
+    pub(crate) fn id(&self) -> u8 {
+        self.id
+    }
+}
+
+impl Drop for Tracked<'_> {
+    fn drop(&mut self) {
+        let drops = &self.drops[usize::from(self.id)];
+        drops.set(drops.get() + 1);
+    }
+}
+
+/// A collection element whose destructor panics, for the `should_panic`
+/// harnesses described in the module docs.
+#[derive(Debug)]
+pub(crate) struct PanicOnDrop(pub(crate) u8);
+
+impl Drop for PanicOnDrop {
+    fn drop(&mut self) {
+        panic!("dropped a `PanicOnDrop`");
+    }
+}
+
+/// Asserts that the elements starting at `ptr` carry exactly `ids`, in order.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `ids.len()` initialized elements.
+pub(crate) unsafe fn assert_ids(ptr: *const Tracked<'_>, ids: &[u8]) {
+    for (i, id) in ids.iter().enumerate() {
+        // SAFETY: guaranteed by the caller.
+        assert_eq!(unsafe { (*ptr.add(i)).id }, *id);
+    }
+}
+
+/// Asserts that every element with an id in `ids` has been dropped exactly
+/// once, except for those in `live`, which must not have been dropped at all.
+pub(crate) fn assert_drops(drops: &[Cell<u8>], ids: Range<u8>, live: &[u8]) {
+    for id in ids {
+        let expected = if live.contains(&id) { 0 } else { 1 };
+        assert_eq!(drops[usize::from(id)].get(), expected);
+    }
+}
+
+/// Raw-pointer round-trip harnesses for `Rc` or `Arc`, named by `$rc`, and the
+/// `Weak` of the same module, one module of them per listed `T`. Each pointer
+/// gets up to two more strong and two more weak references first, so the
+/// counts the contracts look at are not just the initial ones.
+macro_rules! check_rc_raw_round_trip {
+    ($rc:ident; $($name:ident: $ty:ty = $value:expr;)*) => {$(
+        mod $name {
+            use super::*;
+
+            fn any_refs() -> ($rc<$ty>, [Option<$rc<$ty>>; 2], [Option<Weak<$ty>>; 2]) {
+                let this: $rc<$ty> = $value;
+                let others = [(); 2].map(|()| kani::any::<bool>().then(|| $rc::clone(&this)));
+                let weaks = [(); 2].map(|()| kani::any::<bool>().then(|| $rc::downgrade(&this)));
+                (this, others, weaks)
+            }
+
+            #[kani::proof_for_contract($rc::<$ty>::into_raw)]
+            fn check_into_raw() {
+                let (this, _others, _weaks) = any_refs();
+                let _ = $rc::into_raw(this);
+            }
+
+            #[kani::proof_for_contract($rc::<$ty>::into_raw_with_allocator)]
+            fn check_into_raw_with_allocator() {
+                let (this, _others, _weaks) = any_refs();
+                let _ = $rc::into_raw_with_allocator(this);
+            }
+
+            #[kani::proof_for_contract($rc::<$ty>::from_raw)]
+            fn check_from_raw() {
+                let (this, _others, _weaks) = any_refs();
+                let _ = unsafe { $rc::<$ty>::from_raw($rc::into_raw(this)) };
+            }
+
+            #[kani::proof_for_contract($rc::<$ty>::from_raw_in)]
+            fn check_from_raw_in() {
+                let (this, _others, _weaks) = any_refs();
+                let (ptr, alloc) = $rc::into_raw_with_allocator(this);
+                let _ = unsafe { $rc::<$ty>::from_raw_in(ptr, alloc) };
+            }
+
+            #[kani::proof_for_contract($rc::<$ty>::increment_strong_count)]
+            fn check_increment_strong_count() {
+                let (this, _others, _weaks) = any_refs();
+                unsafe { $rc::<$ty>::increment_strong_count($rc::as_ptr(&this)) };
+            }
+
+            /// Dropping the last strong reference drops the value, so this is
+            /// checked through the other references rather than a contract.
+            #[kani::proof]
+            fn check_decrement_strong_count() {
+                let (this, others, weaks) = any_refs();
+                let strong = $rc::strong_count(&this);
+                unsafe { $rc::<$ty>::decrement_strong_count($rc::into_raw(this)) };
+                for other in others.iter().flatten() {
+                    assert_eq!($rc::strong_count(other), strong - 1);
+                }
+                for weak in weaks.iter().flatten() {
+                    assert_eq!(weak.upgrade().is_some(), strong > 1);
+                }
+            }
+
+            #[kani::proof_for_contract(Weak::<$ty>::into_raw)]
+            fn check_weak_into_raw() {
+                let (this, _others, _weaks) = any_refs();
+                let _ = $rc::downgrade(&this).into_raw();
+            }
+
+            #[kani::proof_for_contract(Weak::<$ty>::from_raw)]
+            fn check_weak_from_raw() {
+                let (this, _others, _weaks) = any_refs();
+                let ptr = $rc::downgrade(&this).into_raw();
+                if kani::any() {
+                    drop(this);
+                }
+                let _ = unsafe { Weak::<$ty>::from_raw(ptr) };
+            }
+
+            #[kani::proof_for_contract($rc::<$ty>::get_mut)]
+            fn check_get_mut() {
+                let (mut this, _others, _weaks) = any_refs();
+                let _ = $rc::get_mut(&mut this);
+            }
+
+            #[kani::proof_for_contract($rc::<$ty>::get_mut_unchecked)]
+            fn check_get_mut_unchecked() {
+                let (mut this, _others, _weaks) = any_refs();
+                let _ = unsafe { $rc::get_mut_unchecked(&mut this) };
+            }
+
+            #[kani::proof_for_contract(data_offset::<$ty>)]
+            fn check_data_offset() {
+                let (this, _others, _weaks) = any_refs();
+                let _ = unsafe { data_offset($rc::as_ptr(&this)) };
+            }
+        }
+    )*};
+}
+pub(crate) use check_rc_raw_round_trip;
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::mem;
+
+    use super::*;
+
+    #[kani::proof_for_contract(<ModelAlloc as Allocator>::allocate)]
+    #[kani::unwind(5)]
+    fn check_allocate() {
+        let model = if kani::any() { ModelAlloc::new() } else { ModelAlloc::fallible() };
+        let _ = model.allocate(any_layout::<16>());
+    }
+
+    #[kani::proof_for_contract(<ModelAlloc as Allocator>::deallocate)]
+    #[kani::unwind(5)]
+    fn check_deallocate() {
+        let model = ModelAlloc::new();
+        let layout = any_layout::<16>();
+        let block = model.allocate(layout).unwrap();
+        unsafe { model.deallocate(block.cast(), layout) };
+    }
+
+    /// The default `grow` and `shrink` go through `allocate` and
+    /// `deallocate`, so a resized block replaces the old one rather than
+    /// adding to it, and a failed resize leaves the old block untouched.
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_grow_and_shrink() {
+        let model = ModelAlloc::fallible();
+        let old = any_layout::<8>();
+        let Ok(block) = model.allocate(old) else {
+            assert_eq!(model.live(), 0);
+            return;
+        };
+        let new = Layout::from_size_align(old.size() + kani::any_where(|n| *n <= 8), old.align())
+            .unwrap();
+        let (ptr, layout) = match unsafe { model.grow(block.cast(), old, new) } {
+            Ok(grown) => (grown.cast(), new),
+            Err(AllocError) => (block.cast(), old),
+        };
+        assert_eq!(model.live(), 1);
+        assert_eq!(model.layout_of(ptr), Some(layout));
+
+        let (ptr, layout) = match unsafe { model.shrink(ptr, layout, old) } {
+            Ok(shrunk) => (shrunk.cast(), old),
+            Err(AllocError) => (ptr, layout),
+        };
+        assert_eq!(model.live(), 1);
+        unsafe { model.deallocate(ptr, layout) };
+        model.assert_no_leaks();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_global_alloc() {
+        let model = ModelAlloc::new();
+        let layout = Layout::from_size_align(kani::any_where(|s| *s > 0 && *s <= 8), 4).unwrap();
+        unsafe {
+            let ptr = GlobalAlloc::alloc(&model, layout);
+            assert!(!ptr.is_null());
+            ptr.write_bytes(0xA5, layout.size());
+            let ptr = GlobalAlloc::realloc(&model, ptr, layout, 16);
+            assert!(!ptr.is_null());
+            assert_eq!(*ptr, 0xA5);
+            GlobalAlloc::dealloc(&model, ptr, Layout::from_size_align(16, 4).unwrap());
+        }
+        model.assert_no_leaks();
+    }
+
+    /// Deallocating with a layout other than the one a block was allocated
+    /// with is caught by the model.
+    #[kani::proof]
+    #[kani::unwind(5)]
+    #[kani::should_panic]
+    fn check_wrong_layout_is_rejected() {
+        let model = ModelAlloc::new();
+        let layout = any_layout::<8>();
+        let block = model.allocate(layout).unwrap();
+        let other = any_layout::<8>();
+        kani::assume(other != layout);
+        unsafe { model.deallocate(block.cast(), other) };
+    }
+
+    #[kani::proof]
+    fn check_tracked_counts_drops() {
+        let drops = [const { Cell::new(0) }; 2];
+        let first = Tracked::new(0, &drops);
+        let second = Tracked::new(1, &drops);
+        let keep_second = kani::any();
+        drop(first);
+        if !keep_second {
+            drop(second);
+            assert_drops(&drops, 0..2, &[]);
+        } else {
+            assert_drops(&drops, 0..2, &[1]);
+            mem::forget(second);
+        }
+    }
+}
@@ -200,10 +200,16 @@ use core::ops::{
 use core::pin::{Pin, PinCoerceUnsized};
 use core::ptr::{self, NonNull, Unique};
 use core::task::{Context, Poll};
+#[allow(unused_imports)]
+use core::ub_checks;
+
+use safety::{ensures, requires};
 
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::handle_alloc_error;
 use crate::alloc::{AllocError, Allocator, Global, Layout};
+#[cfg(kani)]
+use crate::kani;
 use crate::raw_vec::RawVec;
 #[cfg(not(no_global_oom_handling))]
 use crate::str::from_boxed_utf8_unchecked;
@@ -232,6 +238,47 @@ pub struct Box<
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
 >(Unique<T>, A);
 
+/// Whether a `Box` in this allocator may own `raw`, as far as can be checked.
+#[cfg(kani)]
+trait BoxAllocation {
+    /// Unless `T`'s layout is zero-sized, `raw` must be writable for it, and
+    /// otherwise it only has to be non-null and aligned. That it is also the
+    /// start of a block of exactly `Layout::for_value_raw(raw)`, which is what
+    /// the `Box` frees it with, only an allocator that knows its blocks can
+    /// tell.
+    fn owns_box<T: ?Sized>(&self, raw: *mut T) -> bool;
+}
+
+#[cfg(kani)]
+impl<A: Allocator> BoxAllocation for A {
+    default fn owns_box<T: ?Sized>(&self, raw: *mut T) -> bool {
+        // SAFETY: the metadata of `raw` is valid, as every caller of the
+        // contracts using this has to guarantee.
+        let layout = unsafe { Layout::for_value_raw(raw) };
+        let start = raw as *const u8;
+        if layout.size() == 0 {
+            !start.is_null() && start.addr() % layout.align() == 0
+        } else {
+            ub_checks::can_write(raw)
+        }
+    }
+}
+
+/// The model allocator of the harnesses knows its blocks, so it also checks
+/// that `raw` starts one with the layout the `Box` frees it with.
+#[cfg(kani)]
+impl BoxAllocation for &crate::alloc::model::ModelAlloc {
+    fn owns_box<T: ?Sized>(&self, raw: *mut T) -> bool {
+        // SAFETY: see above.
+        let layout = unsafe { Layout::for_value_raw(raw) };
+        if layout.size() == 0 {
+            Global.owns_box(raw)
+        } else {
+            NonNull::new(raw).is_some_and(|raw| self.owns(raw.cast(), layout))
+        }
+    }
+}
+
 impl<T> Box<T> {
     /// Allocates memory on the heap and then places `x` into it.
     ///
@@ -930,6 +977,8 @@ impl<T, A: Allocator> Box<mem::MaybeUninit<T>, A> {
     /// ```
     #[stable(feature = "new_uninit", since = "1.82.0")]
     #[inline]
+    #[requires(ub_checks::can_dereference((**self).as_ptr()))]
+    #[ensures(|result: &Box<T, A>| ptr::eq(&**result, old((**self).as_ptr())))]
     pub unsafe fn assume_init(self) -> Box<T, A> {
         let (raw, alloc) = Box::into_raw_with_allocator(self);
         unsafe { Box::from_raw_in(raw as *mut T, alloc) }
@@ -1003,6 +1052,8 @@ impl<T, A: Allocator> Box<[mem::MaybeUninit<T>], A> {
     /// ```
     #[stable(feature = "new_uninit", since = "1.82.0")]
     #[inline]
+    #[requires(ub_checks::can_dereference(&raw const **self as *const [T]))]
+    #[ensures(|result: &Box<[T], A>| ptr::eq(&**result, old(&raw const **self as *const [T])))]
     pub unsafe fn assume_init(self) -> Box<[T], A> {
         let (raw, alloc) = Box::into_raw_with_allocator(self);
         unsafe { Box::from_raw_in(raw as *mut [T], alloc) }
@@ -1054,6 +1105,8 @@ impl<T: ?Sized> Box<T> {
     #[stable(feature = "box_raw", since = "1.4.0")]
     #[inline]
     #[must_use = "call `drop(Box::from_raw(ptr))` if you intend to drop the `Box`"]
+    #[requires(Global.owns_box(raw))]
+    #[ensures(|result: &Self| ptr::eq(&**result, raw))]
     pub unsafe fn from_raw(raw: *mut T) -> Self {
         unsafe { Self::from_raw_in(raw, Global) }
     }
@@ -1107,6 +1160,8 @@ impl<T: ?Sized> Box<T> {
     #[unstable(feature = "box_vec_non_null", reason = "new API", issue = "130364")]
     #[inline]
     #[must_use = "call `drop(Box::from_non_null(ptr))` if you intend to drop the `Box`"]
+    #[requires(Global.owns_box(ptr.as_ptr()))]
+    #[ensures(|result: &Self| ptr::eq(&**result, ptr.as_ptr()))]
     pub unsafe fn from_non_null(ptr: NonNull<T>) -> Self {
         unsafe { Self::from_raw(ptr.as_ptr()) }
     }
@@ -1163,6 +1218,8 @@ impl<T: ?Sized, A: Allocator> Box<T, A> {
     #[unstable(feature = "allocator_api", issue = "32838")]
     #[rustc_const_unstable(feature = "const_box", issue = "92521")]
     #[inline]
+    #[requires(alloc.owns_box(raw))]
+    #[ensures(|result: &Self| ptr::eq(&**result, raw))]
     pub const unsafe fn from_raw_in(raw: *mut T, alloc: A) -> Self {
         Box(unsafe { Unique::new_unchecked(raw) }, alloc)
     }
@@ -1217,6 +1274,8 @@ impl<T: ?Sized, A: Allocator> Box<T, A> {
     // #[unstable(feature = "box_vec_non_null", reason = "new API", issue = "130364")]
     #[rustc_const_unstable(feature = "const_box", issue = "92521")]
     #[inline]
+    #[requires(alloc.owns_box(raw.as_ptr()))]
+    #[ensures(|result: &Self| ptr::eq(&**result, raw.as_ptr()))]
     pub const unsafe fn from_non_null_in(raw: NonNull<T>, alloc: A) -> Self {
         // SAFETY: guaranteed by the caller.
         unsafe { Box::from_raw_in(raw.as_ptr(), alloc) }
@@ -1386,6 +1445,8 @@ impl<T: ?Sized, A: Allocator> Box<T, A> {
     #[must_use = "losing the pointer will leak memory"]
     #[unstable(feature = "allocator_api", issue = "32838")]
     #[inline]
+    #[ensures(|(raw, _): &(*mut T, A)| ptr::eq(*raw, old(&raw const *b)))]
+    #[ensures(|(raw, alloc): &(*mut T, A)| alloc.owns_box(*raw))]
     pub fn into_raw_with_allocator(b: Self) -> (*mut T, A) {
         let mut b = mem::ManuallyDrop::new(b);
         // We carefully get the raw pointer out in a way that Miri's aliasing model understands what
@@ -2128,3 +2189,122 @@ impl<E: Error> Error for Box<E> {
         Error::provide(&**self, request);
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::alloc::model::ModelAlloc;
+
+    /// Asserts that `raw` is the start of a live `model` allocation whose
+    /// layout is exactly the one `Box` frees it with, unless that layout is
+    /// zero-sized and nothing was allocated at all.
+    fn assert_box_layout<T: ?Sized>(model: &ModelAlloc, raw: *mut T) {
+        let layout = unsafe { Layout::for_value_raw(raw) };
+        if layout.size() == 0 {
+            assert_eq!(model.live(), 0);
+        } else {
+            assert_eq!(model.layout_of(NonNull::new(raw).unwrap().cast()), Some(layout));
+        }
+    }
+
+    /// Takes a `Box` of each listed type apart into a pointer and rebuilds it.
+    /// With a custom allocator this runs on the model allocator, which checks
+    /// that the rebuilt `Box` frees its block with the layout it was allocated
+    /// with.
+    macro_rules! check_box_raw_round_trip {
+        ($($name:ident: $ty:ty = $value:expr;)*) => {$(
+            mod $name {
+                use super::*;
+
+                #[kani::proof_for_contract(Box::<$ty>::from_raw)]
+                fn check_from_raw() {
+                    let boxed: Box<$ty> = Box::new($value);
+                    let raw = Box::into_raw(boxed);
+                    kani::cover!(Global.owns_box(raw));
+                    let _ = unsafe { Box::<$ty>::from_raw(raw) };
+                }
+
+                #[kani::proof_for_contract(Box::<$ty>::from_non_null)]
+                fn check_from_non_null() {
+                    let boxed: Box<$ty> = Box::new($value);
+                    let ptr = Box::into_non_null(boxed);
+                    kani::cover!(Global.owns_box(ptr.as_ptr()));
+                    let _ = unsafe { Box::<$ty>::from_non_null(ptr) };
+                }
+
+                #[kani::proof_for_contract(Box::<$ty, &ModelAlloc>::from_raw_in)]
+                fn check_from_raw_in() {
+                    let model = ModelAlloc::new();
+                    let boxed: Box<$ty, &ModelAlloc> = Box::new_in($value, &model);
+                    let (raw, alloc) = Box::into_raw_with_allocator(boxed);
+                    kani::cover!(alloc.owns_box(raw));
+                    drop(unsafe { Box::<$ty, &ModelAlloc>::from_raw_in(raw, alloc) });
+                    model.assert_no_leaks();
+                }
+
+                #[kani::proof_for_contract(Box::<$ty, &ModelAlloc>::from_non_null_in)]
+                fn check_from_non_null_in() {
+                    let model = ModelAlloc::new();
+                    let boxed: Box<$ty, &ModelAlloc> = Box::new_in($value, &model);
+                    let (raw, alloc) = Box::into_non_null_with_allocator(boxed);
+                    kani::cover!(alloc.owns_box(raw.as_ptr()));
+                    drop(unsafe { Box::<$ty, &ModelAlloc>::from_non_null_in(raw, alloc) });
+                    model.assert_no_leaks();
+                }
+
+                #[kani::proof_for_contract(Box::<$ty, &ModelAlloc>::into_raw_with_allocator)]
+                fn check_into_raw_with_allocator() {
+                    let model = ModelAlloc::new();
+                    let boxed: Box<$ty, &ModelAlloc> = Box::new_in($value, &model);
+                    let (raw, alloc) = Box::into_raw_with_allocator(boxed);
+                    assert_box_layout(&model, raw);
+                    drop(unsafe { Box::from_raw_in(raw, alloc) });
+                }
+            }
+        )*};
+    }
+
+    check_box_raw_round_trip! {
+        u8s: u8 = kani::any::<u8>();
+        u64s: u64 = kani::any::<u64>();
+        units: () = ();
+        slices: [u16] = kani::any::<[u16; 3]>();
+        empty_slices: [u16] = [0u16; 0];
+        trait_objects: dyn fmt::Debug = kani::any::<u32>();
+    }
+
+    #[kani::proof_for_contract(Box::<mem::MaybeUninit<u64>, &ModelAlloc>::assume_init)]
+    fn check_assume_init() {
+        let model = ModelAlloc::new();
+        let value = kani::any::<u64>();
+        let mut boxed = Box::<u64, _>::new_uninit_in(&model);
+        boxed.write(value);
+        let boxed = unsafe { boxed.assume_init() };
+        assert_eq!(*boxed, value);
+        let (raw, alloc) = Box::into_raw_with_allocator(boxed);
+        assert_box_layout(&model, raw);
+        drop(unsafe { Box::from_raw_in(raw, alloc) });
+        model.assert_no_leaks();
+    }
+
+    const MAX_LEN: usize = 3;
+
+    #[kani::proof_for_contract(Box::<[mem::MaybeUninit<u16>], &ModelAlloc>::assume_init)]
+    #[kani::unwind(4)]
+    fn check_assume_init_slice() {
+        let model = ModelAlloc::new();
+        let values = kani::any::<[u16; MAX_LEN]>();
+        let len = kani::any_where(|len| *len <= MAX_LEN);
+        let mut boxed = Box::<[u16], _>::new_uninit_slice_in(len, &model);
+        for (slot, value) in boxed.iter_mut().zip(values) {
+            slot.write(value);
+        }
+        let boxed = unsafe { boxed.assume_init() };
+        assert_eq!(*boxed, values[..len]);
+        let (raw, alloc) = Box::into_raw_with_allocator(boxed);
+        assert_box_layout(&model, raw);
+        drop(unsafe { Box::from_raw_in(raw, alloc) });
+        model.assert_no_leaks();
+    }
+}
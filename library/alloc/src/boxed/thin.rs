@@ -14,8 +14,14 @@ use core::mem;
 use core::mem::SizedTypeProperties;
 use core::ops::{Deref, DerefMut};
 use core::ptr::{self, NonNull, Pointee};
+#[allow(unused_imports)]
+use core::ub_checks;
+
+use safety::{ensures, requires};
 
 use crate::alloc::{self, Layout, LayoutError};
+#[cfg(kani)]
+use crate::kani;
 
 /// ThinBox.
 ///
@@ -227,6 +233,8 @@ impl WithOpaqueHeader {
 
 impl<H> WithHeader<H> {
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|result: &WithHeader<H>| ub_checks::can_dereference(result.header()))]
+    #[ensures(|result: &WithHeader<H>| ub_checks::can_dereference(result.value().cast::<T>()))]
     fn new<T>(header: H, value: T) -> WithHeader<H> {
         let value_layout = Layout::new::<T>();
         let Ok((layout, value_offset)) = Self::alloc_layout(value_layout) else {
@@ -270,6 +278,12 @@ impl<H> WithHeader<H> {
 
     /// Non-panicking version of `new`.
     /// Any error is returned as `Err(core::alloc::AllocError)`.
+    #[ensures(|result: &Result<WithHeader<H>, core::alloc::AllocError>| {
+        result.as_ref().map_or(true, |with_header| {
+            ub_checks::can_dereference(with_header.header())
+                && ub_checks::can_dereference(with_header.value().cast::<T>())
+        })
+    })]
     fn try_new<T>(header: H, value: T) -> Result<WithHeader<H>, core::alloc::AllocError> {
         let value_layout = Layout::new::<T>();
         let Ok((layout, value_offset)) = Self::alloc_layout(value_layout) else {
@@ -310,6 +324,8 @@ impl<H> WithHeader<H> {
 
     // `Dyn` is `?Sized` type like `[u32]`, and `T` is ZST type like `[u32; 0]`.
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|result: &WithHeader<H>| ub_checks::can_dereference(result.header()))]
+    #[ensures(|result: &WithHeader<H>| result.value().cast::<T>().is_aligned())]
     fn new_unsize_zst<Dyn, T>(value: T) -> WithHeader<H>
     where
         Dyn: Pointee<Metadata = H> + ?Sized,
@@ -363,6 +379,8 @@ impl<H> WithHeader<H> {
     // Safety:
     // - Assumes that either `value` can be dereferenced, or is the
     //   `NonNull::dangling()` we use when both `T` and `H` are ZSTs.
+    #[requires(ptr::addr_eq(value, self.0.as_ptr()))]
+    #[requires(ub_checks::can_dereference(value))]
     unsafe fn drop<T: ?Sized>(&self, value: *mut T) {
         struct DropGuard<H> {
             ptr: NonNull<u8>,
@@ -403,6 +421,8 @@ impl<H> WithHeader<H> {
         }
     }
 
+    #[ensures(|header: &*mut H| header.is_aligned())]
+    #[ensures(|header: &*mut H| header.wrapping_add(1).cast::<u8>() == self.0.as_ptr())]
     fn header(&self) -> *mut H {
         //  Safety:
         //  - At least `size_of::<H>()` bytes are allocated ahead of the pointer.
@@ -424,6 +444,14 @@ impl<H> WithHeader<H> {
         mem::size_of::<H>()
     }
 
+    #[ensures(|result: &Result<(Layout, usize), LayoutError>| {
+        result.as_ref().map_or(true, |&(layout, value_offset)| {
+            value_offset >= Self::header_size()
+                && value_offset % value_layout.align() == 0
+                && layout.align() >= mem::align_of::<H>().max(value_layout.align())
+                && layout.size() >= value_offset + value_layout.size()
+        })
+    })]
     fn alloc_layout(value_layout: Layout) -> Result<(Layout, usize), LayoutError> {
         Layout::new::<H>().extend(value_layout)
     }
@@ -435,3 +463,154 @@ impl<T: ?Sized + Error> Error for ThinBox<T> {
         self.deref().source()
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::any::Any;
+    use core::cell::Cell;
+
+    use super::*;
+    use crate::alloc::model::{Tracked, any_layout};
+
+    /// Contract harnesses for `WithHeader<$header>` holding a `$value`, over
+    /// headers that are smaller, larger and less or more aligned than the
+    /// value, including zero-sized ones on either side.
+    macro_rules! check_with_header {
+        ($($name:ident: $header:ty, $value:ty;)*) => {$(
+            mod $name {
+                use super::*;
+
+                #[kani::proof_for_contract(WithHeader::<$header>::alloc_layout)]
+                fn check_alloc_layout() {
+                    let _ = WithHeader::<$header>::alloc_layout(any_layout::<16>());
+                }
+
+                #[kani::proof_for_contract(WithHeader::<$header>::new::<$value>)]
+                fn check_new() {
+                    let _ = WithHeader::new(kani::any::<$header>(), kani::any::<$value>());
+                }
+
+                #[kani::proof_for_contract(WithHeader::<$header>::try_new::<$value>)]
+                fn check_try_new() {
+                    let _ = WithHeader::try_new(kani::any::<$header>(), kani::any::<$value>());
+                }
+
+                #[kani::proof_for_contract(WithHeader::<$header>::header)]
+                fn check_header() {
+                    let with_header =
+                        WithHeader::new(kani::any::<$header>(), kani::any::<$value>());
+                    let _ = with_header.header();
+                }
+
+                #[kani::proof_for_contract(WithHeader::<$header>::drop::<$value>)]
+                fn check_drop() {
+                    let with_header =
+                        WithHeader::new(kani::any::<$header>(), kani::any::<$value>());
+                    unsafe { with_header.drop::<$value>(with_header.value().cast()) };
+                }
+            }
+        )*};
+    }
+
+    check_with_header! {
+        unit_unit: (), ();
+        unit_u64: (), u64;
+        usize_unit: usize, ();
+        usize_u8: usize, u8;
+        u8_u64: u8, u64;
+        usize_u16s: usize, [u16; 3];
+    }
+
+    /// Asserts that the metadata of `thin` sits directly before its value,
+    /// and that both are aligned for their types.
+    fn assert_header<T: ?Sized>(thin: &ThinBox<T>) {
+        let header = thin.with_header().header();
+        assert!(header.is_aligned());
+        assert_eq!(header.wrapping_add(1).cast::<u8>(), thin.data());
+        let value: &T = thin;
+        assert_eq!(ptr::from_ref(value).cast::<u8>(), thin.data().cast_const());
+        assert_eq!(thin.data().addr() % mem::align_of_val(value), 0);
+    }
+
+    /// `ThinBox::new` for sized values, whose metadata is `()`, so the value
+    /// sits at the very start of its allocation.
+    macro_rules! check_sized {
+        ($($name:ident: $ty:ty;)*) => {$(
+            #[kani::proof]
+            fn $name() {
+                let value = kani::any::<$ty>();
+                let mut thin = ThinBox::new(value);
+                assert_eq!(mem::size_of_val(&thin), mem::size_of::<*const ()>());
+                assert_header(&thin);
+                assert_eq!(*thin, value);
+                let other = kani::any::<$ty>();
+                *thin = other;
+                assert_eq!(*thin, other);
+            }
+        )*};
+    }
+
+    check_sized! {
+        check_sized_u8: u8;
+        check_sized_u64: u64;
+        check_sized_unit: ();
+        check_sized_array: [u16; 3];
+        check_sized_pair: (u8, u64);
+    }
+
+    #[kani::proof]
+    fn check_unsize_slice() {
+        let values = kani::any::<[u16; 3]>();
+        let mut thin = ThinBox::<[u16]>::new_unsize(values);
+        assert_header(&thin);
+        assert_eq!(thin.meta(), values.len());
+        assert_eq!(*thin, values);
+        thin[2] = !values[2];
+        assert_eq!(thin[..2], values[..2]);
+        assert_eq!(thin[2], !values[2]);
+    }
+
+    /// Zero-sized values take the path that never allocates and reads the
+    /// metadata from a static instead.
+    #[kani::proof]
+    fn check_unsize_empty_slice() {
+        let thin = ThinBox::<[u16]>::new_unsize([0u16; 0]);
+        assert_header(&thin);
+        assert_eq!(thin.len(), 0);
+    }
+
+    #[kani::proof]
+    fn check_unsize_trait_object() {
+        let value = kani::any::<u64>();
+        let mut thin = ThinBox::<dyn Any>::new_unsize(value);
+        assert_header(&thin);
+        assert_eq!(mem::size_of_val(&*thin), mem::size_of::<u64>());
+        assert_eq!(thin.downcast_ref::<u64>(), Some(&value));
+        *thin.downcast_mut::<u64>().unwrap() = !value;
+        assert_eq!(thin.downcast_ref::<u64>(), Some(&!value));
+    }
+
+    #[kani::proof]
+    fn check_unsize_zero_sized_trait_object() {
+        let thin = ThinBox::<dyn Any>::new_unsize(());
+        assert_header(&thin);
+        assert!(thin.is::<()>());
+    }
+
+    /// Dropping a `ThinBox` drops the value it holds exactly once, whether
+    /// its type is known or erased behind the metadata in the header.
+    #[kani::proof]
+    fn check_drop() {
+        let drops = [const { Cell::new(0) }; 2];
+        let sized = ThinBox::new(Tracked::new(0, &drops));
+        let erased = ThinBox::<dyn Debug + '_>::new_unsize(Tracked::new(1, &drops));
+        assert_header(&sized);
+        assert_header(&erased);
+        assert_eq!(sized.id(), 0);
+        drop(sized);
+        assert_eq!(drops[0].get(), 1);
+        drop(erased);
+        assert_eq!(drops[1].get(), 1);
+    }
+}
@@ -272,3 +272,47 @@ impl<T, A: Allocator> ExactSizeIterator for Drain<'_, T, A> {}
 
 #[stable(feature = "fused", since = "1.26.0")]
 impl<T, A: Allocator> FusedIterator for Drain<'_, T, A> {}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::cell::Cell;
+
+    use super::*;
+    use crate::alloc::model::{ModelAlloc, assert_drops};
+    use crate::collections::vec_deque::verify::{MAX_CAP, any_tracked_deque, assert_deque};
+    use crate::kani;
+
+    /// Whatever was taken from either end before the `Drain` is dropped, the
+    /// deque is left valid, holding the elements outside the drained range in
+    /// order, which may have to be joined back together across the end of
+    /// the buffer, and every drained element is dropped exactly once.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_drop_part_way() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let mut deque = any_tracked_deque(&drops, 0, &model);
+        let len = deque.len() as u8;
+        let end = kani::any_where(|end| *end <= len);
+        let start = kani::any_where(|start| *start <= end);
+        {
+            let mut drain = deque.drain(usize::from(start)..usize::from(end));
+            for _ in 0..kani::any_where(|front: &usize| *front <= MAX_CAP) {
+                drop(drain.next());
+            }
+            for _ in 0..kani::any_where(|back: &usize| *back <= MAX_CAP) {
+                drop(drain.next_back());
+            }
+        }
+        assert_deque(&deque, (0..start).chain(end..len));
+        let mut kept = [0; MAX_CAP];
+        for (i, id) in (0..start).chain(end..len).enumerate() {
+            kept[i] = id;
+        }
+        assert_drops(&drops, 0..len, &kept[..deque.len()]);
+        drop(deque);
+        assert_drops(&drops, 0..len, &[]);
+        model.assert_no_leaks();
+    }
+}
@@ -17,10 +17,16 @@ use core::iter::{ByRefSized, repeat_n, repeat_with};
 use core::mem;
 use core::mem::{ManuallyDrop, SizedTypeProperties};
 use core::ops::{Index, IndexMut, Range, RangeBounds};
+#[allow(unused_imports)]
+use core::ub_checks::{self, Invariant};
 use core::{fmt, ptr, slice};
 
+use safety::{ensures, requires};
+
 use crate::alloc::{Allocator, Global};
 use crate::collections::{TryReserveError, TryReserveErrorKind};
+#[cfg(kani)]
+use crate::kani;
 use crate::raw_vec::RawVec;
 use crate::vec::Vec;
 
@@ -146,6 +152,17 @@ unsafe impl<#[may_dangle] T, A: Allocator> Drop for VecDeque<T, A> {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T, A: Allocator> Invariant for VecDeque<T, A> {
+    /// The buffer is a valid `RawVec`, `head` is a physical index into it (or
+    /// zero if there is no buffer), and the elements fit in it. As for `Vec`,
+    /// which slots are initialized is left to the harnesses.
+    fn is_safe(&self) -> bool {
+        let cap = self.capacity();
+        self.buf.is_safe() && (self.head < cap || (cap == 0 && self.head == 0)) && self.len <= cap
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> Default for VecDeque<T> {
     /// Creates an empty deque.
@@ -162,12 +179,21 @@ impl<T, A: Allocator> VecDeque<T, A> {
         self.buf.ptr()
     }
 
+    /// The whole buffer, as a target for `kani::modifies` clauses.
+    #[cfg(kani)]
+    fn allocation(&self) -> *mut [T] {
+        ptr::slice_from_raw_parts_mut(self.ptr(), self.capacity())
+    }
+
     /// Appends an element to the buffer.
     ///
     /// # Safety
     ///
     /// May only be called if `deque.len() < deque.capacity()`
     #[inline]
+    #[requires(self.is_safe() && self.len < self.capacity())]
+    #[ensures(|_| self.is_safe() && self.len == old(self.len) + 1)]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     unsafe fn push_unchecked(&mut self, element: T) {
         // SAFETY: Because of the precondition, it's guaranteed that there is space
         // in the logical array after the last element.
@@ -178,12 +204,16 @@ impl<T, A: Allocator> VecDeque<T, A> {
 
     /// Moves an element out of the buffer
     #[inline]
+    #[requires(off < self.capacity())]
+    #[requires(ub_checks::can_dereference(self.ptr().wrapping_add(off)))]
     unsafe fn buffer_read(&mut self, off: usize) -> T {
         unsafe { ptr::read(self.ptr().add(off)) }
     }
 
     /// Writes an element into the buffer, moving it.
     #[inline]
+    #[requires(off < self.capacity())]
+    #[cfg_attr(kani, kani::modifies(self.allocation()))]
     unsafe fn buffer_write(&mut self, off: usize, value: T) {
         unsafe {
             ptr::write(self.ptr().add(off), value);
@@ -193,6 +223,8 @@ impl<T, A: Allocator> VecDeque<T, A> {
     /// Returns a slice pointer into the buffer.
     /// `range` must lie inside `0..self.capacity()`.
     #[inline]
+    #[requires(range.start <= range.end && range.end <= self.capacity())]
+    #[ensures(|slice: &*mut [T]| slice.len() == old(range.end - range.start))]
     unsafe fn buffer_range(&self, range: Range<usize>) -> *mut [T] {
         unsafe {
             ptr::slice_from_raw_parts_mut(self.ptr().add(range.start), range.end - range.start)
@@ -208,11 +240,15 @@ impl<T, A: Allocator> VecDeque<T, A> {
     /// Returns the index in the underlying buffer for a given logical element
     /// index + addend.
     #[inline]
+    #[requires((idx < self.capacity() && addend <= self.capacity()) || (idx == 0 && addend == 0))]
+    #[ensures(|result: &usize| *result < self.capacity() || *result == 0)]
     fn wrap_add(&self, idx: usize, addend: usize) -> usize {
         wrap_index(idx.wrapping_add(addend), self.capacity())
     }
 
     #[inline]
+    #[requires(self.is_safe() && idx <= self.capacity())]
+    #[ensures(|result: &usize| *result < self.capacity() || *result == 0)]
     fn to_physical_idx(&self, idx: usize) -> usize {
         self.wrap_add(self.head, idx)
     }
@@ -226,6 +262,9 @@ impl<T, A: Allocator> VecDeque<T, A> {
 
     /// Copies a contiguous block of memory len long from src to dst
     #[inline]
+    #[requires(src <= self.capacity() && len <= self.capacity() - src)]
+    #[requires(dst <= self.capacity() && len <= self.capacity() - dst)]
+    #[cfg_attr(kani, kani::modifies(self.allocation()))]
     unsafe fn copy(&mut self, src: usize, dst: usize, len: usize) {
         debug_assert!(
             dst + len <= self.capacity(),
@@ -250,6 +289,10 @@ impl<T, A: Allocator> VecDeque<T, A> {
 
     /// Copies a contiguous block of memory len long from src to dst
     #[inline]
+    #[requires(src <= self.capacity() && len <= self.capacity() - src)]
+    #[requires(dst <= self.capacity() && len <= self.capacity() - dst)]
+    #[requires(T::IS_ZST || src.abs_diff(dst) >= len)]
+    #[cfg_attr(kani, kani::modifies(self.allocation()))]
     unsafe fn copy_nonoverlapping(&mut self, src: usize, dst: usize, len: usize) {
         debug_assert!(
             dst + len <= self.capacity(),
@@ -275,6 +318,16 @@ impl<T, A: Allocator> VecDeque<T, A> {
     /// Copies a potentially wrapping block of memory len long from src to dest.
     /// (abs(dst - src) + len) must be no larger than capacity() (There must be at
     /// most one continuous overlapping region between src and dest).
+    #[requires(if self.capacity() == 0 {
+        src == 0 && dst == 0 && len == 0
+    } else {
+        src < self.capacity()
+            && dst < self.capacity()
+            && len
+                <= self.capacity()
+                    - cmp::min(src.abs_diff(dst), self.capacity() - src.abs_diff(dst))
+    })]
+    #[cfg_attr(kani, kani::modifies(self.allocation()))]
     unsafe fn wrap_copy(&mut self, src: usize, dst: usize, len: usize) {
         debug_assert!(
             cmp::min(src.abs_diff(dst), self.capacity() - src.abs_diff(dst)) + len
@@ -492,6 +545,10 @@ impl<T, A: Allocator> VecDeque<T, A> {
     /// Frobs the head and tail sections around to handle the fact that we
     /// just reallocated. Unsafe because it trusts old_capacity.
     #[inline]
+    #[requires(old_capacity <= self.capacity() && self.len <= old_capacity)]
+    #[requires(self.head < old_capacity || (old_capacity == 0 && self.head == 0))]
+    #[ensures(|_| self.is_safe() && self.len == old(self.len))]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     unsafe fn handle_capacity_increase(&mut self, old_capacity: usize) {
         let new_capacity = self.capacity();
         debug_assert!(new_capacity >= old_capacity);
@@ -2779,6 +2836,12 @@ impl<T: Clone, A: Allocator> VecDeque<T, A> {
 
 /// Returns the index in the underlying buffer for a given logical element index.
 #[inline]
+#[requires(
+    (logical_index == 0 && capacity == 0)
+        || logical_index < capacity
+        || (logical_index - capacity) < capacity
+)]
+#[ensures(|result: &usize| *result < capacity || (*result == 0 && capacity == 0))]
 fn wrap_index(logical_index: usize, capacity: usize) -> usize {
     debug_assert!(
         (logical_index == 0 && capacity == 0)
@@ -3079,3 +3142,264 @@ impl<T, const N: usize> From<[T; N]> for VecDeque<T> {
         deq
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::cell::Cell;
+
+    use super::*;
+    use crate::alloc::model::{ModelAlloc, Tracked, assert_drops};
+
+    /// Largest capacity of the deques built by the harnesses.
+    pub(super) const MAX_CAP: usize = 4;
+
+    /// A deque of bytes with up to `MAX_CAP` capacity, whose whole buffer is
+    /// initialized, and with an arbitrary `head` and `len`.
+    fn any_deque<A: Allocator>(alloc: A) -> VecDeque<u8, A> {
+        let mut deque = VecDeque::with_capacity_in(kani::any_where(|cap| *cap <= MAX_CAP), alloc);
+        let cap = deque.capacity();
+        for off in 0..cap {
+            unsafe { deque.buffer_write(off, kani::any()) };
+        }
+        deque.head = if cap == 0 { 0 } else { kani::any_where(|head| *head < cap) };
+        deque.len = kani::any_where(|len| *len <= cap);
+        deque
+    }
+
+    /// A deque with up to `MAX_CAP` capacity whose tracked elements, with ids
+    /// starting at `first`, start at an arbitrary physical index, so they may
+    /// wrap around the end of the buffer.
+    pub(super) fn any_tracked_deque<'a, A: Allocator>(
+        drops: &'a [Cell<u8>],
+        first: u8,
+        alloc: A,
+    ) -> VecDeque<Tracked<'a>, A> {
+        let mut deque = VecDeque::with_capacity_in(kani::any_where(|cap| *cap <= MAX_CAP), alloc);
+        let cap = deque.capacity();
+        deque.head = if cap == 0 { 0 } else { kani::any_where(|head| *head < cap) };
+        let len = kani::any_where(|len| *len <= cap);
+        for id in first..first + len as u8 {
+            unsafe { deque.push_unchecked(Tracked::new(id, drops)) };
+        }
+        deque
+    }
+
+    /// Asserts that `deque` is valid and holds the elements with `ids`, in
+    /// order.
+    pub(super) fn assert_deque<A: Allocator>(
+        deque: &VecDeque<Tracked<'_>, A>,
+        ids: impl Iterator<Item = u8>,
+    ) {
+        assert!(deque.is_safe());
+        assert!(deque.iter().map(Tracked::id).eq(ids));
+    }
+
+    #[kani::proof_for_contract(wrap_index)]
+    fn check_wrap_index() {
+        let _ = wrap_index(kani::any(), kani::any());
+    }
+
+    #[kani::proof_for_contract(VecDeque::<u8>::wrap_add)]
+    #[kani::unwind(5)]
+    fn check_wrap_add() {
+        let deque = any_deque(Global);
+        let _ = deque.wrap_add(kani::any(), kani::any());
+    }
+
+    #[kani::proof_for_contract(VecDeque::<u8>::to_physical_idx)]
+    #[kani::unwind(5)]
+    fn check_to_physical_idx() {
+        let deque = any_deque(Global);
+        let _ = deque.to_physical_idx(kani::any());
+    }
+
+    #[kani::proof_for_contract(VecDeque::<u8>::push_unchecked)]
+    #[kani::unwind(5)]
+    fn check_push_unchecked() {
+        let mut deque = any_deque(Global);
+        unsafe { deque.push_unchecked(kani::any()) };
+    }
+
+    #[kani::proof_for_contract(VecDeque::<u8>::buffer_read)]
+    #[kani::unwind(5)]
+    fn check_buffer_read() {
+        let mut deque = any_deque(Global);
+        let _ = unsafe { deque.buffer_read(kani::any()) };
+    }
+
+    #[kani::proof_for_contract(VecDeque::<u8>::buffer_write)]
+    #[kani::unwind(5)]
+    fn check_buffer_write() {
+        let mut deque = any_deque(Global);
+        unsafe { deque.buffer_write(kani::any(), kani::any()) };
+    }
+
+    #[kani::proof_for_contract(VecDeque::<u8>::buffer_range)]
+    #[kani::unwind(5)]
+    fn check_buffer_range() {
+        let deque = any_deque(Global);
+        let _ = unsafe { deque.buffer_range(kani::any()..kani::any()) };
+    }
+
+    #[kani::proof_for_contract(VecDeque::<u8>::copy)]
+    #[kani::unwind(5)]
+    fn check_copy() {
+        let mut deque = any_deque(Global);
+        unsafe { deque.copy(kani::any(), kani::any(), kani::any()) };
+    }
+
+    #[kani::proof_for_contract(VecDeque::<u8>::copy_nonoverlapping)]
+    #[kani::unwind(5)]
+    fn check_copy_nonoverlapping() {
+        let mut deque = any_deque(Global);
+        unsafe { deque.copy_nonoverlapping(kani::any(), kani::any(), kani::any()) };
+    }
+
+    #[kani::proof_for_contract(VecDeque::<u8>::wrap_copy)]
+    #[kani::unwind(5)]
+    fn check_wrap_copy() {
+        let mut deque = any_deque(Global);
+        unsafe { deque.wrap_copy(kani::any(), kani::any(), kani::any()) };
+    }
+
+    #[kani::proof_for_contract(VecDeque::<u8>::handle_capacity_increase)]
+    #[kani::unwind(9)]
+    fn check_handle_capacity_increase() {
+        let mut deque = any_deque(Global);
+        let old_capacity = deque.capacity();
+        deque.buf.reserve(deque.len, kani::any_where(|additional| *additional <= MAX_CAP));
+        unsafe { deque.handle_capacity_increase(old_capacity) };
+    }
+
+    /// `wrap_copy` moves the block to `dst` and leaves everything outside of
+    /// it alone, in each of the eight combinations of whether `dst` comes
+    /// after `src` and of which of the two blocks wrap, all of which are
+    /// reachable within `MAX_CAP`.
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_wrap_copy_moves_block() {
+        let mut deque = any_deque(Global);
+        let cap = deque.capacity();
+        kani::assume(cap > 0);
+        let src = kani::any_where(|src| *src < cap);
+        let dst = kani::any_where(|dst| *dst < cap);
+        let len = kani::any_where(|len| {
+            *len <= cap - cmp::min(src.abs_diff(dst), cap - src.abs_diff(dst))
+        });
+        let mut before = [0; MAX_CAP];
+        before[..cap].copy_from_slice(unsafe { &*deque.buffer_range(0..cap) });
+
+        let dst_after_src = deque.wrap_sub(dst, src) < len;
+        let src_wraps = cap - src < len;
+        let dst_wraps = cap - dst < len;
+        kani::cover!(!dst_after_src && !src_wraps && !dst_wraps);
+        kani::cover!(dst_after_src && !src_wraps && !dst_wraps);
+        kani::cover!(!dst_after_src && !src_wraps && dst_wraps);
+        kani::cover!(dst_after_src && !src_wraps && dst_wraps);
+        kani::cover!(!dst_after_src && src_wraps && !dst_wraps);
+        kani::cover!(dst_after_src && src_wraps && !dst_wraps);
+        kani::cover!(!dst_after_src && src_wraps && dst_wraps);
+        kani::cover!(dst_after_src && src_wraps && dst_wraps);
+
+        unsafe { deque.wrap_copy(src, dst, len) };
+        let after = unsafe { &*deque.buffer_range(0..cap) };
+        for off in 0..cap {
+            let from_dst = (off + cap - dst) % cap;
+            if from_dst < len {
+                assert_eq!(after[off], before[(src + from_dst) % cap]);
+            } else {
+                assert_eq!(after[off], before[off]);
+            }
+        }
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    fn check_reserve() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let mut deque = any_tracked_deque(&drops, 0, &model);
+        let len = deque.len();
+        let additional = kani::any_where(|additional| *additional <= MAX_CAP);
+        deque.reserve(additional);
+        assert!(deque.capacity() >= len + additional);
+        assert_deque(&deque, 0..len as u8);
+        drop(deque);
+        assert_drops(&drops, 0..len as u8, &[]);
+        model.assert_no_leaks();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_make_contiguous() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let mut deque = any_tracked_deque(&drops, 0, &model);
+        let len = deque.len();
+        let slice = deque.make_contiguous();
+        assert!(slice.iter().map(Tracked::id).eq(0..len as u8));
+        assert!(deque.as_slices().1.is_empty());
+        assert_deque(&deque, 0..len as u8);
+        drop(deque);
+        assert_drops(&drops, 0..len as u8, &[]);
+        model.assert_no_leaks();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_rotate() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let mut deque = any_tracked_deque(&drops, 0, &model);
+        let len = deque.len() as u8;
+        let n = kani::any_where(|n| *n <= len);
+        if kani::any() {
+            deque.rotate_left(usize::from(n));
+            assert_deque(&deque, (n..len).chain(0..n));
+        } else {
+            deque.rotate_right(usize::from(n));
+            assert_deque(&deque, (len - n..len).chain(0..len - n));
+        }
+        drop(deque);
+        assert_drops(&drops, 0..len, &[]);
+        model.assert_no_leaks();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_split_off() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let mut deque = any_tracked_deque(&drops, 0, &model);
+        let len = deque.len() as u8;
+        let at = kani::any_where(|at| *at <= len);
+        let other = deque.split_off(usize::from(at));
+        assert_deque(&deque, 0..at);
+        assert_deque(&other, at..len);
+        drop(deque);
+        assert_drops(&drops, 0..at, &[]);
+        drop(other);
+        assert_drops(&drops, 0..len, &[]);
+        model.assert_no_leaks();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(9)]
+    fn check_append() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; 2 * MAX_CAP];
+        let mut deque = any_tracked_deque(&drops, 0, &model);
+        let first = deque.len() as u8;
+        let mut other = any_tracked_deque(&drops, first, &model);
+        let len = first + other.len() as u8;
+        deque.append(&mut other);
+        assert_deque(&deque, 0..len);
+        assert_deque(&other, 0..0);
+        drop(other);
+        assert!(drops.iter().all(|drops| drops.get() == 0));
+        drop(deque);
+        assert_drops(&drops, 0..len, &[]);
+        model.assert_no_leaks();
+    }
+}
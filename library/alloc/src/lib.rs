@@ -154,6 +154,7 @@
 #![feature(try_trait_v2)]
 #![feature(try_with_capacity)]
 #![feature(tuple_trait)]
+#![feature(ub_checks)]
 #![feature(unicode_internals)]
 #![feature(unsize)]
 #![feature(unwrap_infallible)]
@@ -211,6 +212,10 @@ extern crate test;
 #[cfg(test)]
 mod testing;
 
+// Verification harnesses use the `kani` module exported by `core`.
+#[cfg(kani)]
+use core::kani;
+
 // Module with internal macros used by other modules (needs to be included before other modules).
 #[macro_use]
 mod macros;
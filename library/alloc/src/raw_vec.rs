@@ -3,7 +3,10 @@
 use core::marker::PhantomData;
 use core::mem::{ManuallyDrop, MaybeUninit, SizedTypeProperties};
 use core::ptr::{self, NonNull, Unique};
-use core::{cmp, hint};
+use core::ub_checks::Invariant;
+use core::{cmp, hint, ub_checks};
+
+use safety::{ensures, requires};
 
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::handle_alloc_error;
@@ -15,6 +18,9 @@ use crate::collections::TryReserveErrorKind::*;
 #[cfg(test)]
 mod tests;
 
+#[cfg(kani)]
+use crate::kani;
+
 // One central function responsible for reporting capacity overflows. This'll
 // ensure that the code generation related to these panics is minimal as there's
 // only one location which panics rather than a bunch throughout the module.
@@ -45,6 +51,7 @@ impl Cap {
     /// `Cap(cap)`, except if `T` is a ZST then `Cap::ZERO`.
     ///
     /// # Safety: cap must be <= `isize::MAX`.
+    #[requires(cap <= isize::MAX as usize)]
     unsafe fn new<T>(cap: usize) -> Self {
         if T::IS_ZST { Cap::ZERO } else { unsafe { Self(cap) } }
     }
@@ -255,6 +262,11 @@ impl<T, A: Allocator> RawVec<T, A> {
     /// If the `ptr` and `capacity` come from a `RawVec` created via `alloc`, then this is
     /// guaranteed.
     #[inline]
+    #[requires(!ptr.is_null() && ptr.is_aligned())]
+    #[requires(T::IS_ZST || capacity <= isize::MAX as usize / size_of::<T>())]
+    #[requires(T::IS_ZST || capacity == 0
+        || ub_checks::can_write(ptr::slice_from_raw_parts_mut(ptr, capacity)))]
+    #[ensures(|raw_vec: &Self| raw_vec.is_safe() && raw_vec.ptr() == ptr)]
     pub unsafe fn from_raw_parts_in(ptr: *mut T, capacity: usize, alloc: A) -> Self {
         // SAFETY: Precondition passed to the caller
         unsafe {
@@ -273,6 +285,10 @@ impl<T, A: Allocator> RawVec<T, A> {
     ///
     /// See [`RawVec::from_raw_parts_in`].
     #[inline]
+    #[requires(ptr.is_aligned() && (T::IS_ZST || capacity <= isize::MAX as usize / size_of::<T>()))]
+    #[requires(T::IS_ZST || capacity == 0
+        || ub_checks::can_write(NonNull::slice_from_raw_parts(ptr, capacity).as_ptr()))]
+    #[ensures(|raw_vec: &Self| raw_vec.is_safe() && raw_vec.non_null() == ptr)]
     pub unsafe fn from_nonnull_in(ptr: NonNull<T>, capacity: usize, alloc: A) -> Self {
         // SAFETY: Precondition passed to the caller
         unsafe {
@@ -331,6 +347,9 @@ impl<T, A: Allocator> RawVec<T, A> {
     #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[track_caller]
+    #[requires(self.is_safe() && len <= self.capacity())]
+    #[ensures(|_| self.is_safe() && self.capacity() - len >= additional)]
+    #[cfg_attr(kani, kani::modifies(self))]
     pub fn reserve(&mut self, len: usize, additional: usize) {
         self.inner.reserve(len, additional, T::LAYOUT)
     }
@@ -345,6 +364,9 @@ impl<T, A: Allocator> RawVec<T, A> {
     }
 
     /// The same as `reserve`, but returns on errors instead of panicking or aborting.
+    #[requires(self.is_safe() && len <= self.capacity())]
+    #[ensures(|result| self.is_safe() && (result.is_err() || self.capacity() - len >= additional))]
+    #[cfg_attr(kani, kani::modifies(self))]
     pub fn try_reserve(&mut self, len: usize, additional: usize) -> Result<(), TryReserveError> {
         self.inner.try_reserve(len, additional, T::LAYOUT)
     }
@@ -373,6 +395,9 @@ impl<T, A: Allocator> RawVec<T, A> {
     }
 
     /// The same as `reserve_exact`, but returns on errors instead of panicking or aborting.
+    #[requires(self.is_safe() && len <= self.capacity())]
+    #[ensures(|result| self.is_safe() && (result.is_err() || self.capacity() - len >= additional))]
+    #[cfg_attr(kani, kani::modifies(self))]
     pub fn try_reserve_exact(
         &mut self,
         len: usize,
@@ -399,6 +424,13 @@ impl<T, A: Allocator> RawVec<T, A> {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T, A: Allocator> Invariant for RawVec<T, A> {
+    fn is_safe(&self) -> bool {
+        self.inner.is_valid(T::LAYOUT)
+    }
+}
+
 unsafe impl<#[may_dangle] T, A: Allocator> Drop for RawVec<T, A> {
     /// Frees the memory owned by the `RawVec` *without* trying to drop its contents.
     fn drop(&mut self) {
@@ -490,6 +522,7 @@ impl<A: Allocator> RawVecInner<A> {
     }
 
     #[inline]
+    #[requires(!ptr.is_null())]
     unsafe fn from_raw_parts_in(ptr: *mut u8, cap: Cap, alloc: A) -> Self {
         Self { ptr: unsafe { Unique::new_unchecked(ptr) }, cap, alloc }
     }
@@ -519,7 +552,33 @@ impl<A: Allocator> RawVecInner<A> {
         &self.alloc
     }
 
+    /// Checks the invariant of a `RawVecInner` that holds elements of `elem_layout`:
+    ///
+    /// * the buffer size `cap * elem_layout.size()` does not exceed `isize::MAX`,
+    ///   so it is a valid `Layout`;
+    /// * zero-sized elements never record a capacity, `capacity()` reports
+    ///   `usize::MAX` for them instead;
+    /// * `ptr` is aligned for the element type, and when the buffer is not
+    ///   empty it points to a live allocation of exactly the buffer size.
+    fn is_valid(&self, elem_layout: Layout) -> bool {
+        let Some(size) = elem_layout.size().checked_mul(self.cap.0) else {
+            return false;
+        };
+        size <= isize::MAX as usize
+            && (elem_layout.size() != 0 || self.cap.0 == 0)
+            && self.ptr.as_ptr().addr() % elem_layout.align() == 0
+            && (size == 0
+                || ub_checks::can_write(ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), size)))
+    }
+
     #[inline]
+    #[requires(self.is_valid(elem_layout))]
+    #[ensures(|memory: &Option<(NonNull<u8>, Layout)>| match *memory {
+        None => elem_layout.size() == 0 || self.cap.0 == 0,
+        Some((ptr, layout)) => ptr == self.ptr.as_non_null_ptr()
+            && layout.size() == elem_layout.size() * self.cap.0
+            && layout.align() == elem_layout.align(),
+    })]
     fn current_memory(&self, elem_layout: Layout) -> Option<(NonNull<u8>, Layout)> {
         if elem_layout.size() == 0 || self.cap.0 == 0 {
             None
@@ -625,6 +684,9 @@ impl<A: Allocator> RawVecInner<A> {
     }
 
     #[inline]
+    #[requires(cap <= isize::MAX as usize)]
+    #[ensures(|_| self.ptr.as_ptr() == ptr.as_mut_ptr() && self.cap.0 == cap)]
+    #[cfg_attr(kani, kani::modifies(self))]
     unsafe fn set_ptr_and_cap(&mut self, ptr: NonNull<[u8]>, cap: usize) {
         // Allocators currently return a `NonNull<[u8]>` whose length matches
         // the size requested. If that ever changes, the capacity here should
@@ -633,6 +695,12 @@ impl<A: Allocator> RawVecInner<A> {
         self.cap = unsafe { Cap(cap) };
     }
 
+    #[requires(self.is_valid(elem_layout) && additional > 0)]
+    #[ensures(|_| self.is_valid(elem_layout))]
+    #[ensures(|result| result.is_ok() || self.cap.0 == old(self.cap.0))]
+    #[ensures(|result| result.is_err()
+        || len.checked_add(additional).is_some_and(|cap| self.cap.0 >= cap))]
+    #[cfg_attr(kani, kani::modifies(self))]
     fn grow_amortized(
         &mut self,
         len: usize,
@@ -665,6 +733,11 @@ impl<A: Allocator> RawVecInner<A> {
         Ok(())
     }
 
+    #[requires(self.is_valid(elem_layout))]
+    #[ensures(|_| self.is_valid(elem_layout))]
+    #[ensures(|result| result.is_ok() || self.cap.0 == old(self.cap.0))]
+    #[ensures(|result| result.is_err() || len.checked_add(additional) == Some(self.cap.0))]
+    #[cfg_attr(kani, kani::modifies(self))]
     fn grow_exact(
         &mut self,
         len: usize,
@@ -690,6 +763,10 @@ impl<A: Allocator> RawVecInner<A> {
 
     #[cfg(not(no_global_oom_handling))]
     #[inline]
+    #[requires(self.is_valid(elem_layout))]
+    #[ensures(|_| self.is_valid(elem_layout))]
+    #[ensures(|result| result.is_err() || elem_layout.size() == 0 || self.cap.0 == cap)]
+    #[cfg_attr(kani, kani::modifies(self))]
     fn shrink(&mut self, cap: usize, elem_layout: Layout) -> Result<(), TryReserveError> {
         assert!(cap <= self.capacity(elem_layout.size()), "Tried to shrink to a larger capacity");
         // SAFETY: Just checked this isn't trying to grow
@@ -748,6 +825,7 @@ impl<A: Allocator> RawVecInner<A> {
     /// after this function returns.
     /// Ideally this function would take `self` by move, but it cannot because it exists to be
     /// called from a `Drop` impl.
+    #[requires(self.is_valid(elem_layout))]
     unsafe fn deallocate(&mut self, elem_layout: Layout) {
         if let Some((ptr, layout)) = self.current_memory(elem_layout) {
             unsafe {
@@ -758,6 +836,12 @@ impl<A: Allocator> RawVecInner<A> {
 }
 
 #[inline(never)]
+#[requires(current_memory.map_or(true, |(_, old_layout)| {
+    old_layout.align() == new_layout.align() && old_layout.size() <= new_layout.size()
+}))]
+#[ensures(|memory: &Result<NonNull<[u8]>, TryReserveError>| {
+    memory.as_ref().map_or(true, |ptr| ptr.len() >= new_layout.size())
+})]
 fn finish_grow<A>(
     new_layout: Layout,
     current_memory: Option<(NonNull<u8>, Layout)>,
@@ -815,3 +899,168 @@ fn alloc_guard(alloc_size: usize) -> Result<(), TryReserveError> {
 fn layout_array(cap: usize, elem_layout: Layout) -> Result<Layout, TryReserveError> {
     elem_layout.repeat(cap).map(|(layout, _pad)| layout).map_err(|_| CapacityOverflow.into())
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::alloc::model::{ModelAlloc, any_layout};
+
+    /// Largest capacity and reservation the harnesses use, to keep the
+    /// allocations small.
+    const MAX_CAP: usize = 4;
+
+    /// An arbitrary element layout: at most 8 bytes, aligned to at most 8, and
+    /// padded to its alignment like the layout of any Rust type.
+    fn any_elem_layout() -> Layout {
+        any_layout::<8>().pad_to_align()
+    }
+
+    /// A `RawVecInner` that either never allocated or owns an allocation of
+    /// up to `MAX_CAP` elements.
+    fn any_inner(elem_layout: Layout, alloc: ModelAlloc) -> RawVecInner<ModelAlloc> {
+        let cap = kani::any_where(|cap| *cap <= MAX_CAP);
+        let inner = RawVecInner::try_with_capacity_in(cap, alloc, elem_layout);
+        kani::assume(inner.is_ok());
+        inner.unwrap()
+    }
+
+    #[kani::proof_for_contract(RawVecInner::<ModelAlloc>::current_memory)]
+    #[kani::unwind(5)]
+    fn check_current_memory() {
+        let elem_layout = any_elem_layout();
+        let inner = any_inner(elem_layout, ModelAlloc::new());
+        let _ = inner.current_memory(elem_layout);
+    }
+
+    #[kani::proof_for_contract(RawVecInner::<ModelAlloc>::grow_amortized)]
+    #[kani::unwind(5)]
+    fn check_grow_amortized() {
+        let elem_layout = any_elem_layout();
+        let mut inner = any_inner(elem_layout, ModelAlloc::fallible());
+        let len = kani::any_where(|len| *len <= inner.capacity(elem_layout.size()));
+        let _ = inner.grow_amortized(len, kani::any_where(|n| *n <= MAX_CAP), elem_layout);
+    }
+
+    #[kani::proof_for_contract(RawVecInner::<ModelAlloc>::grow_exact)]
+    #[kani::unwind(5)]
+    fn check_grow_exact() {
+        let elem_layout = any_elem_layout();
+        let mut inner = any_inner(elem_layout, ModelAlloc::fallible());
+        let len = kani::any_where(|len| *len <= inner.capacity(elem_layout.size()));
+        let _ = inner.grow_exact(len, kani::any_where(|n| *n <= MAX_CAP), elem_layout);
+    }
+
+    #[kani::proof_for_contract(RawVecInner::<ModelAlloc>::shrink)]
+    #[kani::unwind(5)]
+    fn check_shrink() {
+        let elem_layout = any_elem_layout();
+        let mut inner = any_inner(elem_layout, ModelAlloc::new());
+        let cap = kani::any_where(|cap| *cap <= inner.capacity(elem_layout.size()));
+        let _ = inner.shrink(cap, elem_layout);
+    }
+
+    #[kani::proof_for_contract(finish_grow)]
+    #[kani::unwind(5)]
+    fn check_finish_grow() {
+        let elem_layout = any_elem_layout();
+        let mut inner = any_inner(elem_layout, ModelAlloc::new());
+        let current_memory = inner.current_memory(elem_layout);
+        let cap = kani::any_where(|cap| *cap <= 2 * MAX_CAP);
+        kani::assume(cap >= inner.capacity(elem_layout.size()));
+        let new_layout = layout_array(cap, elem_layout).unwrap();
+        let _ = finish_grow(new_layout, current_memory, &mut inner.alloc);
+    }
+
+    #[kani::proof_for_contract(RawVecInner::<ModelAlloc>::set_ptr_and_cap)]
+    fn check_set_ptr_and_cap() {
+        let mut inner = RawVecInner::new_in(ModelAlloc::new(), 1);
+        let mut byte = 0u8;
+        let cap = kani::any_where(|cap| *cap <= 1);
+        let ptr = NonNull::slice_from_raw_parts(NonNull::from(&mut byte), cap);
+        unsafe { inner.set_ptr_and_cap(ptr, cap) };
+    }
+
+    macro_rules! check_raw_vec {
+        ($($name:ident: $ty:ty,)*) => {$(
+            mod $name {
+                use super::*;
+
+                fn any_raw_vec(alloc: ModelAlloc) -> RawVec<$ty, ModelAlloc> {
+                    let cap = kani::any_where(|cap| *cap <= MAX_CAP);
+                    let raw_vec = RawVec::try_with_capacity_in(cap, alloc);
+                    kani::assume(raw_vec.is_ok());
+                    raw_vec.unwrap()
+                }
+
+                #[kani::proof_for_contract(RawVec::<$ty, ModelAlloc>::reserve)]
+                #[kani::unwind(5)]
+                fn check_reserve() {
+                    let mut raw_vec = any_raw_vec(ModelAlloc::new());
+                    let len = kani::any_where(|len| *len <= raw_vec.capacity());
+                    raw_vec.reserve(len, kani::any_where(|n| *n <= MAX_CAP));
+                }
+
+                #[kani::proof_for_contract(RawVec::<$ty, ModelAlloc>::try_reserve)]
+                #[kani::unwind(5)]
+                fn check_try_reserve() {
+                    let mut raw_vec = any_raw_vec(ModelAlloc::fallible());
+                    let len = kani::any_where(|len| *len <= raw_vec.capacity());
+                    let _ = raw_vec.try_reserve(len, kani::any_where(|n| *n <= MAX_CAP));
+                }
+
+                #[kani::proof_for_contract(RawVec::<$ty, ModelAlloc>::try_reserve_exact)]
+                #[kani::unwind(5)]
+                fn check_try_reserve_exact() {
+                    let mut raw_vec = any_raw_vec(ModelAlloc::fallible());
+                    let len = kani::any_where(|len| *len <= raw_vec.capacity());
+                    let _ = raw_vec.try_reserve_exact(len, kani::any_where(|n| *n <= MAX_CAP));
+                }
+
+                #[kani::proof_for_contract(RawVec::<$ty, ModelAlloc>::from_raw_parts_in)]
+                #[kani::unwind(5)]
+                fn check_from_raw_parts_in() {
+                    let raw_vec = ManuallyDrop::new(any_raw_vec(ModelAlloc::new()));
+                    let alloc = unsafe { ptr::read(raw_vec.allocator()) };
+                    let _ = unsafe {
+                        RawVec::from_raw_parts_in(raw_vec.ptr(), raw_vec.capacity(), alloc)
+                    };
+                }
+
+                /// Whatever sequence of reservations and shrinks runs, and
+                /// whether or not each allocation succeeds, the buffer stays
+                /// valid, matches the block the allocator knows about, and is
+                /// freed with the layout it was allocated with.
+                #[kani::proof]
+                #[kani::unwind(5)]
+                fn check_allocation_freed_with_its_layout() {
+                    let model = ModelAlloc::fallible();
+                    let mut raw_vec = RawVec::<$ty, &ModelAlloc>::new_in(&model);
+                    for _ in 0..3 {
+                        let len = kani::any_where(|len| *len <= raw_vec.capacity());
+                        if kani::any() {
+                            let _ = raw_vec.try_reserve(len, kani::any_where(|n| *n <= MAX_CAP));
+                        } else {
+                            let _ = raw_vec.inner.shrink(len, <$ty>::LAYOUT);
+                        }
+                        assert!(raw_vec.is_safe());
+                        match raw_vec.inner.current_memory(<$ty>::LAYOUT) {
+                            Some((ptr, layout)) => assert_eq!(model.layout_of(ptr), Some(layout)),
+                            None => assert_eq!(model.live(), 0),
+                        }
+                    }
+                    drop(raw_vec);
+                    model.assert_no_leaks();
+                }
+            }
+        )*};
+    }
+
+    check_raw_vec! {
+        u8s: u8,
+        u32s: u32,
+        u16_triples: [u16; 3],
+        u64s: u64,
+        units: (),
+    }
+}
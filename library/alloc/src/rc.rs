@@ -260,16 +260,22 @@ use core::pin::PinCoerceUnsized;
 use core::ptr::{self, NonNull, drop_in_place};
 #[cfg(not(no_global_oom_handling))]
 use core::slice::from_raw_parts_mut;
+#[allow(unused_imports)]
+use core::ub_checks::{self, Invariant};
 use core::{borrow, fmt, hint};
 #[cfg(test)]
 use std::boxed::Box;
 
+use safety::{ensures, requires};
+
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::handle_alloc_error;
 use crate::alloc::{AllocError, Allocator, Global, Layout};
 use crate::borrow::{Cow, ToOwned};
 #[cfg(not(test))]
 use crate::boxed::Box;
+#[cfg(kani)]
+use crate::kani;
 #[cfg(not(no_global_oom_handling))]
 use crate::string::String;
 #[cfg(not(no_global_oom_handling))]
@@ -288,6 +294,43 @@ struct RcInner<T: ?Sized> {
     value: T,
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: ?Sized> Invariant for RcInner<T> {
+    /// `strong` counts the `Rc`s, and `weak` counts the `Weak`s plus one
+    /// implicit weak reference that all the `Rc`s hold together. So while
+    /// there is any strong reference, there is at least one weak reference,
+    /// and the allocation outlives the value.
+    fn is_safe(&self) -> bool {
+        self.strong() == 0 || self.weak() >= 1
+    }
+}
+
+/// The `RcInner` that `ptr`, as returned by `Rc::into_raw` or
+/// `Weak::into_raw` for a non-dangling `Weak`, points into.
+#[cfg(kani)]
+fn inner_of<T: ?Sized>(ptr: *const T) -> *const RcInner<T> {
+    // SAFETY: only the alignment is read from the metadata.
+    let offset = data_offset_align(unsafe { align_of_val_raw(ptr) });
+    ptr.wrapping_byte_sub(offset) as *const RcInner<T>
+}
+
+/// Whether `inner` points to a live `RcInner` that still holds its value.
+#[cfg(kani)]
+fn is_live<T: ?Sized>(inner: *const RcInner<T>) -> bool {
+    ub_checks::can_dereference(inner) && {
+        // SAFETY: just checked.
+        let inner = unsafe { &*inner };
+        inner.is_safe() && inner.strong() >= 1
+    }
+}
+
+/// The strong count of the allocation that `ptr` points into.
+#[cfg(kani)]
+fn strong_count_of<T: ?Sized>(ptr: *const T) -> usize {
+    // SAFETY: only used in contracts that require `is_live(inner_of(ptr))`.
+    unsafe { &*inner_of(ptr) }.strong()
+}
+
 /// Calculate layout for `RcInner<T>` using the inner value's layout
 fn rc_inner_layout_for_value_layout(layout: Layout) -> Layout {
     // Calculate layout using the given value layout.
@@ -343,11 +386,15 @@ impl<T: ?Sized + Unsize<U>, U: ?Sized> DispatchFromDyn<Rc<U>> for Rc<T> {}
 
 impl<T: ?Sized> Rc<T> {
     #[inline]
+    #[requires(is_live(ptr.as_ptr()))]
+    #[ensures(|rc| rc.ptr == ptr)]
     unsafe fn from_inner(ptr: NonNull<RcInner<T>>) -> Self {
         unsafe { Self::from_inner_in(ptr, Global) }
     }
 
     #[inline]
+    #[requires(is_live(ptr))]
+    #[ensures(|rc| rc.ptr.as_ptr() == ptr)]
     unsafe fn from_ptr(ptr: *mut RcInner<T>) -> Self {
         unsafe { Self::from_inner(NonNull::new_unchecked(ptr)) }
     }
@@ -368,17 +415,22 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     }
 
     #[inline]
+    #[requires(is_live(ptr.as_ptr()))]
+    #[ensures(|rc| rc.ptr == ptr)]
     unsafe fn from_inner_in(ptr: NonNull<RcInner<T>>, alloc: A) -> Self {
         Self { ptr, phantom: PhantomData, alloc }
     }
 
     #[inline]
+    #[requires(is_live(ptr))]
+    #[ensures(|rc| rc.ptr.as_ptr() == ptr)]
     unsafe fn from_ptr_in(ptr: *mut RcInner<T>, alloc: A) -> Self {
         unsafe { Self::from_inner_in(NonNull::new_unchecked(ptr), alloc) }
     }
 
     // Non-inlined part of `drop`.
     #[inline(never)]
+    #[requires(self.inner().strong() == 0 && self.inner().weak() >= 1)]
     unsafe fn drop_slow(&mut self) {
         // Reconstruct the "strong weak" pointer and drop it when this
         // variable goes out of scope. This ensures that the memory is
@@ -963,6 +1015,7 @@ impl<T, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[stable(feature = "rc_unique", since = "1.4.0")]
+    #[ensures(|result| result.is_ok() == (old(Rc::strong_count(&this)) == 1))]
     pub fn try_unwrap(this: Self) -> Result<T, Self> {
         if Rc::strong_count(&this) == 1 {
             let this = ManuallyDrop::new(this);
@@ -1014,6 +1067,7 @@ impl<T, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[stable(feature = "rc_into_inner", since = "1.70.0")]
+    #[ensures(|result| result.is_some() == (old(Rc::strong_count(&this)) == 1))]
     pub fn into_inner(this: Self) -> Option<T> {
         Rc::try_unwrap(this).ok()
     }
@@ -1301,6 +1355,8 @@ impl<T: ?Sized> Rc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_raw", since = "1.17.0")]
+    #[requires(is_live(inner_of(ptr)))]
+    #[ensures(|rc| Rc::as_ptr(rc) == ptr)]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Self::from_raw_in(ptr, Global) }
     }
@@ -1334,6 +1390,9 @@ impl<T: ?Sized> Rc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_mutate_strong_count", since = "1.53.0")]
+    #[requires(is_live(inner_of(ptr)) && strong_count_of(ptr) < usize::MAX)]
+    #[ensures(|_| strong_count_of(ptr) == old(strong_count_of(ptr)) + 1)]
+    #[cfg_attr(kani, kani::modifies(unsafe { &(*inner_of(ptr)).strong }))]
     pub unsafe fn increment_strong_count(ptr: *const T) {
         unsafe { Self::increment_strong_count_in(ptr, Global) }
     }
@@ -1368,6 +1427,7 @@ impl<T: ?Sized> Rc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_mutate_strong_count", since = "1.53.0")]
+    #[requires(is_live(inner_of(ptr)))]
     pub unsafe fn decrement_strong_count(ptr: *const T) {
         unsafe { Self::decrement_strong_count_in(ptr, Global) }
     }
@@ -1404,6 +1464,7 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     #[must_use = "losing the pointer will leak memory"]
     #[stable(feature = "rc_raw", since = "1.17.0")]
     #[rustc_never_returns_null_ptr]
+    #[ensures(|ptr| !ptr.is_null() && *ptr == old(Rc::as_ptr(&this)))]
     pub fn into_raw(this: Self) -> *const T {
         let this = ManuallyDrop::new(this);
         Self::as_ptr(&*this)
@@ -1429,6 +1490,7 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[must_use = "losing the pointer will leak memory"]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[ensures(|(ptr, _)| !ptr.is_null() && *ptr == old(Rc::as_ptr(&this)))]
     pub fn into_raw_with_allocator(this: Self) -> (*const T, A) {
         let this = mem::ManuallyDrop::new(this);
         let ptr = Self::as_ptr(&this);
@@ -1532,6 +1594,8 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// }
     /// ```
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live(inner_of(ptr)))]
+    #[ensures(|rc| Rc::as_ptr(rc) == ptr)]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         let offset = unsafe { data_offset(ptr) };
 
@@ -1633,6 +1697,9 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live(inner_of(ptr)) && strong_count_of(ptr) < usize::MAX)]
+    #[ensures(|_| strong_count_of(ptr) == old(strong_count_of(ptr)) + 1)]
+    #[cfg_attr(kani, kani::modifies(unsafe { &(*inner_of(ptr)).strong }))]
     pub unsafe fn increment_strong_count_in(ptr: *const T, alloc: A)
     where
         A: Clone,
@@ -1676,6 +1743,7 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live(inner_of(ptr)))]
     pub unsafe fn decrement_strong_count_in(ptr: *const T, alloc: A) {
         unsafe { drop(Rc::from_raw_in(ptr, alloc)) };
     }
@@ -1713,6 +1781,7 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[stable(feature = "rc_unique", since = "1.4.0")]
+    #[ensures(|result| result.is_some() == old(Rc::is_unique(this)))]
     pub fn get_mut(this: &mut Self) -> Option<&mut T> {
         if Rc::is_unique(this) { unsafe { Some(Rc::get_mut_unchecked(this)) } } else { None }
     }
@@ -1779,6 +1848,8 @@ impl<T: ?Sized, A: Allocator> Rc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "get_mut_unchecked", issue = "63292")]
+    #[requires(this.inner().is_safe() && this.inner().strong() >= 1)]
+    #[ensures(|value| ptr::addr_eq(&**value, old(Rc::as_ptr(this))))]
     pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
         // We are careful to *not* create a reference covering the "count" fields, as
         // this would conflict with accesses to the reference counts (e.g. by `Weak`).
@@ -2027,6 +2098,9 @@ impl<T: ?Sized> Rc<T> {
     /// The function `mem_to_rc_inner` is called with the data pointer
     /// and must return back a (potentially fat)-pointer for the `RcInner<T>`.
     #[cfg(not(no_global_oom_handling))]
+    #[ensures(|&inner| {
+        is_live(inner) && unsafe { &*inner }.strong() == 1 && unsafe { &*inner }.weak() == 1
+    })]
     unsafe fn allocate_for_layout(
         value_layout: Layout,
         allocate: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
@@ -2046,6 +2120,9 @@ impl<T: ?Sized> Rc<T> {
     /// The function `mem_to_rc_inner` is called with the data pointer
     /// and must return back a (potentially fat)-pointer for the `RcInner<T>`.
     #[inline]
+    #[ensures(|result| result.as_ref().map_or(true, |&inner| {
+        is_live(inner) && unsafe { &*inner }.strong() == 1 && unsafe { &*inner }.weak() == 1
+    }))]
     unsafe fn try_allocate_for_layout(
         value_layout: Layout,
         allocate: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
@@ -3048,6 +3125,9 @@ impl<T: ?Sized> Weak<T> {
     /// [`new`]: Weak::new
     #[inline]
     #[stable(feature = "weak_into_raw", since = "1.45.0")]
+    #[requires(is_dangling(ptr)
+        || (ub_checks::can_dereference(inner_of(ptr)) && unsafe { &*inner_of(ptr) }.weak() >= 1))]
+    #[ensures(|weak| weak.as_ptr() == ptr)]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Self::from_raw_in(ptr, Global) }
     }
@@ -3132,6 +3212,7 @@ impl<T: ?Sized, A: Allocator> Weak<T, A> {
     /// [`as_ptr`]: Weak::as_ptr
     #[must_use = "losing the pointer will leak memory"]
     #[stable(feature = "weak_into_raw", since = "1.45.0")]
+    #[ensures(|ptr| *ptr == old(self.as_ptr()))]
     pub fn into_raw(self) -> *const T {
         mem::ManuallyDrop::new(self).as_ptr()
     }
@@ -3168,6 +3249,7 @@ impl<T: ?Sized, A: Allocator> Weak<T, A> {
     #[must_use = "losing the pointer will leak memory"]
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[ensures(|(ptr, _)| *ptr == old(self.as_ptr()))]
     pub fn into_raw_with_allocator(self) -> (*const T, A) {
         let this = mem::ManuallyDrop::new(self);
         let result = this.as_ptr();
@@ -3220,6 +3302,9 @@ impl<T: ?Sized, A: Allocator> Weak<T, A> {
     /// [`new`]: Weak::new
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_dangling(ptr)
+        || (ub_checks::can_dereference(inner_of(ptr)) && unsafe { &*inner_of(ptr) }.weak() >= 1))]
+    #[ensures(|weak| weak.as_ptr() == ptr)]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         // See Weak::as_ptr for context on how the input pointer is derived.
 
@@ -3480,6 +3565,9 @@ trait RcInnerPtr {
     }
 
     #[inline]
+    #[requires(self.strong() != 0)]
+    #[ensures(|_| self.strong() == old(self.strong()) + 1)]
+    #[cfg_attr(kani, kani::modifies(self.strong_ref()))]
     fn inc_strong(&self) {
         let strong = self.strong();
 
@@ -3503,6 +3591,9 @@ trait RcInnerPtr {
     }
 
     #[inline]
+    #[requires(self.strong() != 0)]
+    #[ensures(|_| self.strong() == old(self.strong()) - 1)]
+    #[cfg_attr(kani, kani::modifies(self.strong_ref()))]
     fn dec_strong(&self) {
         self.strong_ref().set(self.strong() - 1);
     }
@@ -3513,6 +3604,9 @@ trait RcInnerPtr {
     }
 
     #[inline]
+    #[requires(self.weak() != 0)]
+    #[ensures(|_| self.weak() == old(self.weak()) + 1)]
+    #[cfg_attr(kani, kani::modifies(self.weak_ref()))]
     fn inc_weak(&self) {
         let weak = self.weak();
 
@@ -3536,6 +3630,9 @@ trait RcInnerPtr {
     }
 
     #[inline]
+    #[requires(self.weak() != 0)]
+    #[ensures(|_| self.weak() == old(self.weak()) - 1)]
+    #[cfg_attr(kani, kani::modifies(self.weak_ref()))]
     fn dec_weak(&self) {
         self.weak_ref().set(self.weak() - 1);
     }
@@ -3588,6 +3685,8 @@ impl<T: ?Sized, A: Allocator> Unpin for Rc<T, A> {}
 ///
 /// The pointer must point to (and have valid metadata for) a previously
 /// valid instance of T, but the T is allowed to be dropped.
+#[ensures(|offset| *offset >= mem::size_of::<RcInner<()>>()
+    && *offset % unsafe { align_of_val_raw(ptr) } == 0)]
 unsafe fn data_offset<T: ?Sized>(ptr: *const T) -> usize {
     // Align the unsized value to the end of the RcInner.
     // Because RcInner is repr(C), it will always be the last field in memory.
@@ -3842,3 +3941,247 @@ impl<T: ?Sized, A: Allocator> Drop for UniqueRcUninit<T, A> {
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::alloc::model::{ModelAlloc, check_rc_raw_round_trip};
+
+    fn any_inner() -> RcInner<u8> {
+        RcInner {
+            strong: Cell::new(kani::any_where(|strong| *strong < usize::MAX)),
+            weak: Cell::new(kani::any_where(|weak| *weak < usize::MAX)),
+            value: kani::any(),
+        }
+    }
+
+    #[kani::proof_for_contract(<RcInner<u8> as RcInnerPtr>::inc_strong)]
+    fn check_inc_strong() {
+        any_inner().inc_strong();
+    }
+
+    #[kani::proof_for_contract(<RcInner<u8> as RcInnerPtr>::dec_strong)]
+    fn check_dec_strong() {
+        any_inner().dec_strong();
+    }
+
+    #[kani::proof_for_contract(<RcInner<u8> as RcInnerPtr>::inc_weak)]
+    fn check_inc_weak() {
+        any_inner().inc_weak();
+    }
+
+    #[kani::proof_for_contract(<RcInner<u8> as RcInnerPtr>::dec_weak)]
+    fn check_dec_weak() {
+        any_inner().dec_weak();
+    }
+
+    #[kani::proof_for_contract(Rc::<u8>::from_inner)]
+    fn check_from_inner() {
+        let (ptr, _) = Rc::into_inner_with_allocator(Rc::new(kani::any::<u8>()));
+        let _ = unsafe { Rc::from_inner(ptr) };
+    }
+
+    #[kani::proof_for_contract(Rc::<u8>::from_ptr)]
+    fn check_from_ptr() {
+        let (ptr, _) = Rc::into_inner_with_allocator(Rc::new(kani::any::<u8>()));
+        let _ = unsafe { Rc::from_ptr(ptr.as_ptr()) };
+    }
+
+    #[kani::proof_for_contract(Rc::<u8, &ModelAlloc>::from_inner_in)]
+    fn check_from_inner_in() {
+        let model = ModelAlloc::new();
+        let (ptr, alloc) = Rc::into_inner_with_allocator(Rc::new_in(kani::any::<u8>(), &model));
+        let _ = unsafe { Rc::from_inner_in(ptr, alloc) };
+    }
+
+    #[kani::proof_for_contract(Rc::<u8, &ModelAlloc>::from_ptr_in)]
+    fn check_from_ptr_in() {
+        let model = ModelAlloc::new();
+        let (ptr, alloc) = Rc::into_inner_with_allocator(Rc::new_in(kani::any::<u8>(), &model));
+        let _ = unsafe { Rc::from_ptr_in(ptr.as_ptr(), alloc) };
+    }
+
+    /// `drop_slow` drops the value and frees the allocation, which a
+    /// `modifies` clause cannot describe, so its precondition is set up by hand
+    /// and the harness checks that nothing leaks.
+    #[kani::proof]
+    fn check_drop_slow() {
+        let model = ModelAlloc::new();
+        let mut rc = ManuallyDrop::new(Rc::new_in(kani::any::<u8>(), &model));
+        rc.inner().dec_strong();
+        unsafe { rc.drop_slow() };
+        model.assert_no_leaks();
+    }
+
+    #[kani::proof_for_contract(Rc::<u8>::try_allocate_for_layout)]
+    fn check_try_allocate_for_layout() {
+        let model = ModelAlloc::fallible();
+        let _ = unsafe {
+            Rc::<u8>::try_allocate_for_layout(
+                Layout::new::<u8>(),
+                |layout| model.allocate(layout),
+                |mem| mem.cast(),
+            )
+        };
+    }
+
+    #[kani::proof_for_contract(Rc::<[u16]>::allocate_for_layout)]
+    fn check_allocate_for_layout() {
+        let len = kani::any_where(|len| *len <= 4);
+        let _ = unsafe {
+            Rc::<[u16]>::allocate_for_layout(
+                Layout::array::<u16>(len).unwrap(),
+                |layout| Global.allocate(layout),
+                |mem| ptr::slice_from_raw_parts_mut(mem.cast::<u16>(), len) as *mut RcInner<[u16]>,
+            )
+        };
+    }
+
+    check_rc_raw_round_trip! {
+        Rc;
+        u8s: u8 = Rc::new(kani::any());
+        u64s: u64 = Rc::new(kani::any());
+        slices: [u16] = Rc::from(kani::any::<[u16; 3]>());
+        strs: str = Rc::from("rc");
+        trait_objects: dyn fmt::Debug = Rc::new(kani::any::<u32>());
+    }
+
+    /// `Weak::new` never allocates, and its sentinel pointer survives a round
+    /// trip through `into_raw` and `from_raw`.
+    #[kani::proof]
+    fn check_dangling_weak_round_trip() {
+        let weak = unsafe { Weak::<u64>::from_raw(Weak::new().into_raw()) };
+        assert!(is_dangling(weak.as_ptr()));
+        assert!(weak.upgrade().is_none());
+    }
+
+    type Family<'a> = (
+        Rc<u8, &'a ModelAlloc>,
+        [Option<Rc<u8, &'a ModelAlloc>>; 2],
+        [Option<Weak<u8, &'a ModelAlloc>>; 2],
+    );
+
+    /// An `Rc` together with up to two more strong and two weak references
+    /// to the same allocation.
+    fn any_family(model: &ModelAlloc) -> Family<'_> {
+        let rc = Rc::new_in(kani::any(), model);
+        let others = [(); 2].map(|()| kani::any::<bool>().then(|| Rc::clone(&rc)));
+        let weaks = [(); 2].map(|()| kani::any::<bool>().then(|| Rc::downgrade(&rc)));
+        (rc, others, weaks)
+    }
+
+    #[kani::proof_for_contract(Rc::<u8, &ModelAlloc>::get_mut)]
+    fn check_get_mut_contract() {
+        let model = ModelAlloc::new();
+        let (mut rc, _others, _weaks) = any_family(&model);
+        let _ = Rc::get_mut(&mut rc);
+    }
+
+    #[kani::proof_for_contract(Rc::<u8, &ModelAlloc>::get_mut_unchecked)]
+    fn check_get_mut_unchecked() {
+        let model = ModelAlloc::new();
+        let (mut rc, _others, _weaks) = any_family(&model);
+        let _ = unsafe { Rc::get_mut_unchecked(&mut rc) };
+    }
+
+    /// `get_mut` only hands out a `&mut` when no other `Rc` or `Weak` could
+    /// observe the value through it.
+    #[kani::proof]
+    fn check_get_mut() {
+        let model = ModelAlloc::new();
+        let (mut rc, others, weaks) = any_family(&model);
+        let value = *rc;
+        if let Some(value_mut) = Rc::get_mut(&mut rc) {
+            assert!(others.iter().all(Option::is_none));
+            assert!(weaks.iter().all(Option::is_none));
+            *value_mut = value.wrapping_add(1);
+        }
+        for other in others.iter().flatten() {
+            assert_eq!(**other, value);
+        }
+        drop((rc, others, weaks));
+        model.assert_no_leaks();
+    }
+
+    /// The `&mut` from `make_mut` points into an allocation that no other
+    /// `Rc` shares and no `Weak` can upgrade to, so writing through it is
+    /// never seen through the other references.
+    #[kani::proof]
+    fn check_make_mut() {
+        let model = ModelAlloc::new();
+        let (mut rc, others, weaks) = any_family(&model);
+        let value = *rc;
+        *Rc::make_mut(&mut rc) = value.wrapping_add(1);
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert_eq!(Rc::weak_count(&rc), 0);
+        for other in others.iter().flatten() {
+            assert!(!Rc::ptr_eq(&rc, other));
+            assert_eq!(**other, value);
+        }
+        for weak in weaks.iter().flatten() {
+            assert!(!ptr::addr_eq(weak.as_ptr(), Rc::as_ptr(&rc)));
+            if let Some(upgraded) = weak.upgrade() {
+                assert_eq!(*upgraded, value);
+            }
+        }
+        drop((rc, others, weaks));
+        model.assert_no_leaks();
+    }
+
+    /// `try_unwrap` only moves the value out of a unique `Rc`, after which
+    /// the remaining `Weak`s can no longer reach it. It may free the
+    /// allocation, so its postcondition is checked here rather than through
+    /// a contract harness.
+    #[kani::proof]
+    fn check_try_unwrap() {
+        let model = ModelAlloc::new();
+        let (rc, others, weaks) = any_family(&model);
+        let value = *rc;
+        let strong = Rc::strong_count(&rc);
+        match Rc::try_unwrap(rc) {
+            Ok(unwrapped) => {
+                assert_eq!(strong, 1);
+                assert_eq!(unwrapped, value);
+                for weak in weaks.iter().flatten() {
+                    assert!(weak.upgrade().is_none());
+                }
+            }
+            Err(rc) => {
+                assert_eq!(Rc::strong_count(&rc), strong);
+                assert!(strong > 1);
+                assert_eq!(*rc, value);
+            }
+        }
+        drop((others, weaks));
+        model.assert_no_leaks();
+    }
+
+    /// Calling `into_inner` on every clone of an `Rc`, in any order, returns
+    /// the value exactly once, namely for the clone that was the last one.
+    #[kani::proof]
+    #[kani::unwind(4)]
+    fn check_into_inner_exactly_once() {
+        let model = ModelAlloc::new();
+        let (rc, [first, second], weaks) = any_family(&model);
+        let value = *rc;
+        let mut all = [Some(rc), first, second];
+        all.swap(0, kani::any_where(|i| *i < 3));
+        let mut unwrapped = 0;
+        for rc in all.into_iter().flatten() {
+            let strong = Rc::strong_count(&rc);
+            let inner = Rc::into_inner(rc);
+            assert_eq!(inner.is_some(), strong == 1);
+            if let Some(inner) = inner {
+                assert_eq!(inner, value);
+                unwrapped += 1;
+            }
+        }
+        assert_eq!(unwrapped, 1);
+        for weak in weaks.iter().flatten() {
+            assert!(weak.upgrade().is_none());
+        }
+        drop(weaks);
+        model.assert_no_leaks();
+    }
+}
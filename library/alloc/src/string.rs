@@ -54,14 +54,20 @@ use core::ops::AddAssign;
 use core::ops::Bound::{Excluded, Included, Unbounded};
 use core::ops::{self, Range, RangeBounds};
 use core::str::pattern::{Pattern, Utf8Pattern};
+#[allow(unused_imports)]
+use core::ub_checks::{self, Invariant};
 use core::{fmt, hash, ptr, slice};
 
+use safety::{ensures, requires};
+
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::Allocator;
 #[cfg(not(no_global_oom_handling))]
 use crate::borrow::{Cow, ToOwned};
 use crate::boxed::Box;
 use crate::collections::TryReserveError;
+#[cfg(kani)]
+use crate::kani;
 use crate::str::{self, Chars, Utf8Error, from_utf8_unchecked_mut};
 #[cfg(not(no_global_oom_handling))]
 use crate::str::{FromStr, from_boxed_utf8_unchecked};
@@ -1007,6 +1013,11 @@ impl String {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[requires(!buf.is_null() && length <= capacity && capacity <= isize::MAX as usize)]
+    #[requires(capacity == 0 || ub_checks::can_write(ptr::slice_from_raw_parts_mut(buf, capacity)))]
+    #[requires(ub_checks::can_dereference(ptr::slice_from_raw_parts(buf, length))
+        && str::from_utf8(unsafe { slice::from_raw_parts(buf, length) }).is_ok())]
+    #[ensures(|s: &String| s.is_safe() && s.len() == length && s.as_ptr() == buf)]
     pub unsafe fn from_raw_parts(buf: *mut u8, length: usize, capacity: usize) -> String {
         unsafe { String { vec: Vec::from_raw_parts(buf, length, capacity) } }
     }
@@ -1040,6 +1051,8 @@ impl String {
     #[inline]
     #[must_use]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[requires(str::from_utf8(&bytes).is_ok())]
+    #[ensures(|s: &String| s.is_safe() && s.as_bytes() == old(bytes.clone()))]
     pub unsafe fn from_utf8_unchecked(bytes: Vec<u8>) -> String {
         String { vec: bytes }
     }
@@ -1725,6 +1738,10 @@ impl String {
     }
 
     #[cfg(not(no_global_oom_handling))]
+    #[requires(self.is_safe() && self.is_char_boundary(idx) && str::from_utf8(bytes).is_ok())]
+    #[ensures(|_| self.is_safe() && self.len() == old(self.len()) + bytes.len())]
+    #[ensures(|_| &self.as_bytes()[idx..idx + bytes.len()] == bytes)]
+    #[cfg_attr(kani, kani::modifies(self, self.vec.allocation()))]
     unsafe fn insert_bytes(&mut self, idx: usize, bytes: &[u8]) {
         let len = self.len();
         let amt = bytes.len();
@@ -2482,6 +2499,15 @@ impl_eq! { Cow<'a, str>, &'b str }
 #[cfg(not(no_global_oom_handling))]
 impl_eq! { Cow<'a, str>, String }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl Invariant for String {
+    /// The bytes are valid UTF-8, and the vector holding them is itself valid,
+    /// so in particular its length never exceeds its capacity.
+    fn is_safe(&self) -> bool {
+        self.vec.is_safe() && str::from_utf8(&self.vec).is_ok()
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Default for String {
     /// Creates an empty `String`.
@@ -3217,3 +3243,169 @@ impl From<char> for String {
         c.to_string()
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::mem::{self, ManuallyDrop};
+
+    use super::*;
+
+    /// Largest number of chars in the strings built by the harnesses, so at
+    /// most 12 bytes.
+    const MAX_CHARS: usize = 3;
+
+    /// A string of up to `MAX_CHARS` arbitrary chars.
+    fn any_string() -> String {
+        let mut s = String::new();
+        for _ in 0..kani::any_where(|len: &usize| *len <= MAX_CHARS) {
+            s.push(kani::any());
+        }
+        s
+    }
+
+    /// An arbitrary char boundary of `s`, including `s.len()`.
+    fn any_boundary(s: &str) -> usize {
+        kani::any_where(|idx| s.is_char_boundary(*idx))
+    }
+
+    /// An arbitrary range of `s` that starts and ends on char boundaries.
+    fn any_range(s: &str) -> Range<usize> {
+        let end = any_boundary(s);
+        let start = kani::any_where(|start| *start <= end && s.is_char_boundary(*start));
+        start..end
+    }
+
+    #[kani::proof_for_contract(String::from_raw_parts)]
+    #[kani::unwind(14)]
+    fn check_from_raw_parts() {
+        let mut s = ManuallyDrop::new(any_string());
+        let _ = unsafe { String::from_raw_parts(s.as_mut_ptr(), s.len(), s.capacity()) };
+    }
+
+    #[kani::proof_for_contract(String::from_utf8_unchecked)]
+    #[kani::unwind(6)]
+    fn check_from_utf8_unchecked() {
+        let bytes: [u8; 4] = kani::any();
+        let len = kani::any_where(|len| *len <= bytes.len());
+        let _ = unsafe { String::from_utf8_unchecked(bytes[..len].to_vec()) };
+    }
+
+    #[kani::proof_for_contract(String::insert_bytes)]
+    #[kani::unwind(14)]
+    fn check_insert_bytes() {
+        let mut s = any_string();
+        let other = any_string();
+        let idx = any_boundary(&s);
+        unsafe { s.insert_bytes(idx, other.as_bytes()) };
+    }
+
+    /// Writing through `as_mut_vec` keeps the invariant exactly as long as
+    /// the bytes written keep the string UTF-8.
+    #[kani::proof]
+    #[kani::unwind(14)]
+    fn check_as_mut_vec() {
+        let mut s = any_string();
+        let other = any_string();
+        let idx = any_boundary(&s);
+        let vec = unsafe { s.as_mut_vec() };
+        vec.splice(idx..idx, other.bytes());
+        assert!(s.is_safe());
+
+        let byte: u8 = kani::any();
+        unsafe { s.as_mut_vec() }.push(byte);
+        assert_eq!(s.is_safe(), byte.is_ascii());
+    }
+
+    /// The drained range is only removed when the `Drain` is dropped, so
+    /// leaking it leaves the string as it was.
+    #[kani::proof]
+    #[kani::unwind(14)]
+    fn check_drain() {
+        let mut s = any_string();
+        let original = s.clone();
+        let Range { start, end } = any_range(&s);
+        let mut drain = s.drain(start..end);
+        for _ in 0..kani::any_where(|taken: &usize| *taken <= MAX_CHARS) {
+            drain.next();
+        }
+        if kani::any() {
+            drop(drain);
+            assert_eq!(s[..start], original[..start]);
+            assert_eq!(s[start..], original[end..]);
+        } else {
+            mem::forget(drain);
+            assert_eq!(s, original);
+        }
+        assert!(s.is_safe());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(14)]
+    fn check_replace_range() {
+        let mut s = any_string();
+        let original = s.clone();
+        let replace_with = any_string();
+        let Range { start, end } = any_range(&s);
+        s.replace_range(start..end, &replace_with);
+        assert!(s.is_safe());
+        assert_eq!(s[..start], original[..start]);
+        assert_eq!(s[start..start + replace_with.len()], replace_with[..]);
+        assert_eq!(s[start + replace_with.len()..], original[end..]);
+    }
+
+    /// `retain` hands `f` every char in order and keeps exactly the ones it
+    /// accepts. While it runs, the string is mutably borrowed, so `f` only
+    /// checks the char it is handed.
+    #[kani::proof]
+    #[kani::unwind(14)]
+    fn check_retain() {
+        let mut s = any_string();
+        let original = s.clone();
+        let mut chars = original.chars();
+        let mut kept = String::new();
+        s.retain(|ch| {
+            assert_eq!(Some(ch), chars.next());
+            let keep = kani::any();
+            if keep {
+                kept.push(ch);
+            }
+            keep
+        });
+        assert!(s.is_safe());
+        assert_eq!(s, kept);
+    }
+
+    /// `f` panics on an arbitrary char while `SetLenOnDrop` is armed. On that
+    /// panic the guard truncates the string to the chars kept so far, the
+    /// state `check_retain` reaches with an `f` that rejects every remaining
+    /// char; see `alloc::model`.
+    #[kani::proof]
+    #[kani::unwind(14)]
+    #[kani::should_panic]
+    fn check_retain_panicking() {
+        let mut s = any_string();
+        let panics_at = kani::any_where(|at: &usize| *at < s.chars().count());
+        let mut calls = 0;
+        s.retain(|_| {
+            if calls == panics_at {
+                panic!("`f` panicked");
+            }
+            calls += 1;
+            kani::any()
+        });
+    }
+
+    #[kani::proof]
+    #[kani::unwind(14)]
+    fn check_split_off() {
+        let mut s = any_string();
+        let original = s.clone();
+        let at = any_boundary(&s);
+        let tail = s.split_off(at);
+        assert!(s.is_safe());
+        assert!(tail.is_safe());
+        assert_eq!(s[..], original[..at]);
+        assert_eq!(tail[..], original[at..]);
+    }
+}
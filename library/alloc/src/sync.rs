@@ -26,13 +26,19 @@ use core::ptr::{self, NonNull};
 use core::slice::from_raw_parts_mut;
 use core::sync::atomic;
 use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+#[allow(unused_imports)]
+use core::ub_checks::{self, Invariant};
 use core::{borrow, fmt, hint};
 
+use safety::{ensures, requires};
+
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::handle_alloc_error;
 use crate::alloc::{AllocError, Allocator, Global, Layout};
 use crate::borrow::{Cow, ToOwned};
 use crate::boxed::Box;
+#[cfg(kani)]
+use crate::kani;
 use crate::rc::is_dangling;
 #[cfg(not(no_global_oom_handling))]
 use crate::string::String;
@@ -262,10 +268,14 @@ impl<T: ?Sized + Unsize<U>, U: ?Sized, A: Allocator> CoerceUnsized<Arc<U, A>> fo
 impl<T: ?Sized + Unsize<U>, U: ?Sized> DispatchFromDyn<Arc<U>> for Arc<T> {}
 
 impl<T: ?Sized> Arc<T> {
+    #[requires(is_live(ptr.as_ptr()))]
+    #[ensures(|arc| arc.ptr == ptr)]
     unsafe fn from_inner(ptr: NonNull<ArcInner<T>>) -> Self {
         unsafe { Self::from_inner_in(ptr, Global) }
     }
 
+    #[requires(is_live(ptr))]
+    #[ensures(|arc| arc.ptr.as_ptr() == ptr)]
     unsafe fn from_ptr(ptr: *mut ArcInner<T>) -> Self {
         unsafe { Self::from_ptr_in(ptr, Global) }
     }
@@ -279,11 +289,15 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     }
 
     #[inline]
+    #[requires(is_live(ptr.as_ptr()))]
+    #[ensures(|arc| arc.ptr == ptr)]
     unsafe fn from_inner_in(ptr: NonNull<ArcInner<T>>, alloc: A) -> Self {
         Self { ptr, phantom: PhantomData, alloc }
     }
 
     #[inline]
+    #[requires(is_live(ptr))]
+    #[ensures(|arc| arc.ptr.as_ptr() == ptr)]
     unsafe fn from_ptr_in(ptr: *mut ArcInner<T>, alloc: A) -> Self {
         unsafe { Self::from_inner_in(NonNull::new_unchecked(ptr), alloc) }
     }
@@ -357,6 +371,42 @@ struct ArcInner<T: ?Sized> {
     data: T,
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T: ?Sized> Invariant for ArcInner<T> {
+    /// As for `RcInner`: while there is any strong reference, the `Arc`s
+    /// together hold one weak reference. `usize::MAX` only stands for a
+    /// locked weak count, which `is_unique` takes while it holds the last
+    /// weak reference.
+    fn is_safe(&self) -> bool {
+        self.strong.load(Relaxed) == 0 || self.weak.load(Relaxed) >= 1
+    }
+}
+
+/// The counterpart of `rc::inner_of`, for pointers from `Arc` and `Weak`.
+#[cfg(kani)]
+fn inner_of<T: ?Sized>(ptr: *const T) -> *const ArcInner<T> {
+    // SAFETY: only the alignment is read from the metadata.
+    let offset = data_offset_align(unsafe { align_of_val_raw(ptr) });
+    ptr.wrapping_byte_sub(offset) as *const ArcInner<T>
+}
+
+/// The counterpart of `rc::is_live`.
+#[cfg(kani)]
+fn is_live<T: ?Sized>(inner: *const ArcInner<T>) -> bool {
+    ub_checks::can_dereference(inner) && {
+        // SAFETY: just checked.
+        let inner = unsafe { &*inner };
+        inner.is_safe() && inner.strong.load(Relaxed) >= 1
+    }
+}
+
+/// The counterpart of `rc::strong_count_of`.
+#[cfg(kani)]
+fn strong_count_of<T: ?Sized>(ptr: *const T) -> usize {
+    // SAFETY: only used in contracts that require `is_live(inner_of(ptr))`.
+    unsafe { &*inner_of(ptr) }.strong.load(Relaxed)
+}
+
 /// Calculate layout for `ArcInner<T>` using the inner value's layout
 fn arcinner_layout_for_value_layout(layout: Layout) -> Layout {
     // Calculate layout using the given value layout.
@@ -1420,6 +1470,8 @@ impl<T: ?Sized> Arc<T> {
     /// ```
     #[inline]
     #[stable(feature = "rc_raw", since = "1.17.0")]
+    #[requires(is_live(inner_of(ptr)))]
+    #[ensures(|arc| Arc::as_ptr(arc) == ptr)]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Arc::from_raw_in(ptr, Global) }
     }
@@ -1454,6 +1506,9 @@ impl<T: ?Sized> Arc<T> {
     /// ```
     #[inline]
     #[stable(feature = "arc_mutate_strong_count", since = "1.51.0")]
+    #[requires(is_live(inner_of(ptr)) && strong_count_of(ptr) <= MAX_REFCOUNT)]
+    #[ensures(|_| strong_count_of(ptr) == old(strong_count_of(ptr)) + 1)]
+    #[cfg_attr(kani, kani::modifies(unsafe { &(*inner_of(ptr)).strong }))]
     pub unsafe fn increment_strong_count(ptr: *const T) {
         unsafe { Arc::increment_strong_count_in(ptr, Global) }
     }
@@ -1490,6 +1545,7 @@ impl<T: ?Sized> Arc<T> {
     /// ```
     #[inline]
     #[stable(feature = "arc_mutate_strong_count", since = "1.51.0")]
+    #[requires(is_live(inner_of(ptr)))]
     pub unsafe fn decrement_strong_count(ptr: *const T) {
         unsafe { Arc::decrement_strong_count_in(ptr, Global) }
     }
@@ -1526,6 +1582,7 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     #[must_use = "losing the pointer will leak memory"]
     #[stable(feature = "rc_raw", since = "1.17.0")]
     #[rustc_never_returns_null_ptr]
+    #[ensures(|ptr| !ptr.is_null() && *ptr == old(Arc::as_ptr(&this)))]
     pub fn into_raw(this: Self) -> *const T {
         let this = ManuallyDrop::new(this);
         Self::as_ptr(&*this)
@@ -1551,6 +1608,7 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[must_use = "losing the pointer will leak memory"]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[ensures(|(ptr, _)| !ptr.is_null() && *ptr == old(Arc::as_ptr(&this)))]
     pub fn into_raw_with_allocator(this: Self) -> (*const T, A) {
         let this = mem::ManuallyDrop::new(this);
         let ptr = Self::as_ptr(&this);
@@ -1656,6 +1714,8 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live(inner_of(ptr)))]
+    #[ensures(|arc| Arc::as_ptr(arc) == ptr)]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         unsafe {
             let offset = data_offset(ptr);
@@ -1809,6 +1869,9 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live(inner_of(ptr)) && strong_count_of(ptr) <= MAX_REFCOUNT)]
+    #[ensures(|_| strong_count_of(ptr) == old(strong_count_of(ptr)) + 1)]
+    #[cfg_attr(kani, kani::modifies(unsafe { &(*inner_of(ptr)).strong }))]
     pub unsafe fn increment_strong_count_in(ptr: *const T, alloc: A)
     where
         A: Clone,
@@ -1855,6 +1918,7 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_live(inner_of(ptr)))]
     pub unsafe fn decrement_strong_count_in(ptr: *const T, alloc: A) {
         unsafe { drop(Arc::from_raw_in(ptr, alloc)) };
     }
@@ -1871,6 +1935,7 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
 
     // Non-inlined part of `drop`.
     #[inline(never)]
+    #[requires(self.inner().strong.load(Relaxed) == 0 && self.inner().weak.load(Relaxed) >= 1)]
     unsafe fn drop_slow(&mut self) {
         // Drop the weak ref collectively held by all strong references when this
         // variable goes out of scope. This ensures that the memory is deallocated
@@ -2384,6 +2449,9 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[stable(feature = "arc_unique", since = "1.4.0")]
+    #[ensures(|result| result.is_some()
+        == (old(Arc::strong_count(this)) == 1 && old(Arc::weak_count(this)) == 0))]
+    #[cfg_attr(kani, kani::modifies(&this.inner().weak))]
     pub fn get_mut(this: &mut Self) -> Option<&mut T> {
         if this.is_unique() {
             // This unsafety is ok because we're guaranteed that the pointer
@@ -2459,6 +2527,8 @@ impl<T: ?Sized, A: Allocator> Arc<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "get_mut_unchecked", issue = "63292")]
+    #[requires(this.inner().is_safe() && this.inner().strong.load(Relaxed) >= 1)]
+    #[ensures(|value| ptr::addr_eq(&**value, old(Arc::as_ptr(this))))]
     pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
         // We are careful to *not* create a reference covering the "count" fields, as
         // this would alias with concurrent access to the reference counts (e.g. by `Weak`).
@@ -2757,6 +2827,9 @@ impl<T: ?Sized> Weak<T> {
     /// [`upgrade`]: Weak::upgrade
     #[inline]
     #[stable(feature = "weak_into_raw", since = "1.45.0")]
+    #[requires(is_dangling(ptr) || (ub_checks::can_dereference(inner_of(ptr))
+        && unsafe { &*inner_of(ptr) }.weak.load(Relaxed) >= 1))]
+    #[ensures(|weak| weak.as_ptr() == ptr)]
     pub unsafe fn from_raw(ptr: *const T) -> Self {
         unsafe { Weak::from_raw_in(ptr, Global) }
     }
@@ -2841,6 +2914,7 @@ impl<T: ?Sized, A: Allocator> Weak<T, A> {
     /// [`as_ptr`]: Weak::as_ptr
     #[must_use = "losing the pointer will leak memory"]
     #[stable(feature = "weak_into_raw", since = "1.45.0")]
+    #[ensures(|ptr| *ptr == old(self.as_ptr()))]
     pub fn into_raw(self) -> *const T {
         ManuallyDrop::new(self).as_ptr()
     }
@@ -2876,6 +2950,7 @@ impl<T: ?Sized, A: Allocator> Weak<T, A> {
     /// [`as_ptr`]: Weak::as_ptr
     #[must_use = "losing the pointer will leak memory"]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[ensures(|(ptr, _)| *ptr == old(self.as_ptr()))]
     pub fn into_raw_with_allocator(self) -> (*const T, A) {
         let this = mem::ManuallyDrop::new(self);
         let result = this.as_ptr();
@@ -2928,6 +3003,9 @@ impl<T: ?Sized, A: Allocator> Weak<T, A> {
     /// [`upgrade`]: Weak::upgrade
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(is_dangling(ptr) || (ub_checks::can_dereference(inner_of(ptr))
+        && unsafe { &*inner_of(ptr) }.weak.load(Relaxed) >= 1))]
+    #[ensures(|weak| weak.as_ptr() == ptr)]
     pub unsafe fn from_raw_in(ptr: *const T, alloc: A) -> Self {
         // See Weak::as_ptr for context on how the input pointer is derived.
 
@@ -3870,6 +3948,8 @@ impl<T: ?Sized, A: Allocator> Unpin for Arc<T, A> {}
 ///
 /// The pointer must point to (and have valid metadata for) a previously
 /// valid instance of T, but the T is allowed to be dropped.
+#[ensures(|offset| *offset >= mem::size_of::<ArcInner<()>>()
+    && *offset % unsafe { align_of_val_raw(ptr) } == 0)]
 unsafe fn data_offset<T: ?Sized>(ptr: *const T) -> usize {
     // Align the unsized value to the end of the ArcInner.
     // Because RcInner is repr(C), it will always be the last field in memory.
@@ -3969,3 +4049,319 @@ impl<T: core::error::Error + ?Sized> core::error::Error for Arc<T> {
         core::error::Error::provide(&**self, req);
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::cell::{Cell, RefCell};
+    use core::sync::atomic::AtomicUsize;
+
+    use super::*;
+    use crate::alloc::model::{ModelAlloc, Tracked, check_rc_raw_round_trip};
+
+    #[kani::proof_for_contract(Arc::<u8>::from_inner)]
+    fn check_from_inner() {
+        let (ptr, _) = Arc::into_inner_with_allocator(Arc::new(kani::any::<u8>()));
+        let _ = unsafe { Arc::from_inner(ptr) };
+    }
+
+    #[kani::proof_for_contract(Arc::<u8>::from_ptr)]
+    fn check_from_ptr() {
+        let (ptr, _) = Arc::into_inner_with_allocator(Arc::new(kani::any::<u8>()));
+        let _ = unsafe { Arc::from_ptr(ptr.as_ptr()) };
+    }
+
+    #[kani::proof_for_contract(Arc::<u8, &ModelAlloc>::from_inner_in)]
+    fn check_from_inner_in() {
+        let model = ModelAlloc::new();
+        let (ptr, alloc) = Arc::into_inner_with_allocator(Arc::new_in(kani::any::<u8>(), &model));
+        let _ = unsafe { Arc::from_inner_in(ptr, alloc) };
+    }
+
+    #[kani::proof_for_contract(Arc::<u8, &ModelAlloc>::from_ptr_in)]
+    fn check_from_ptr_in() {
+        let model = ModelAlloc::new();
+        let (ptr, alloc) = Arc::into_inner_with_allocator(Arc::new_in(kani::any::<u8>(), &model));
+        let _ = unsafe { Arc::from_ptr_in(ptr.as_ptr(), alloc) };
+    }
+
+    /// `drop_slow` drops the value and may free the allocation, which a
+    /// `modifies` clause cannot describe, so this sets up its precondition by
+    /// hand and checks that nothing leaks.
+    #[kani::proof]
+    fn check_drop_slow() {
+        let model = ModelAlloc::new();
+        let mut arc = ManuallyDrop::new(Arc::new_in(kani::any::<u8>(), &model));
+        arc.inner().strong.store(0, Relaxed);
+        unsafe { arc.drop_slow() };
+        model.assert_no_leaks();
+    }
+
+    check_rc_raw_round_trip! {
+        Arc;
+        u8s: u8 = Arc::new(kani::any());
+        u64s: u64 = Arc::new(kani::any());
+        slices: [u16] = Arc::from(kani::any::<[u16; 3]>());
+        strs: str = Arc::from("arc");
+        trait_objects: dyn fmt::Debug = Arc::new(kani::any::<u32>());
+    }
+
+    /// The strong count may go up to `MAX_REFCOUNT` before the increment.
+    #[kani::proof_for_contract(Arc::<u8>::increment_strong_count)]
+    fn check_increment_strong_count_up_to_max() {
+        let arc = ManuallyDrop::new(Arc::new(kani::any::<u8>()));
+        arc.inner().strong.store(kani::any_where(|n| (1..=MAX_REFCOUNT).contains(n)), Relaxed);
+        unsafe { Arc::increment_strong_count(Arc::as_ptr(&arc)) };
+    }
+
+    /// Past `MAX_REFCOUNT`, taking another strong reference aborts instead of
+    /// letting the count run on towards an overflow.
+    #[kani::proof]
+    #[kani::should_panic]
+    fn check_increment_strong_count_aborts_past_max() {
+        let arc = ManuallyDrop::new(Arc::new(kani::any::<u8>()));
+        arc.inner().strong.store(kani::any_where(|n| *n > MAX_REFCOUNT), Relaxed);
+        unsafe { Arc::increment_strong_count(Arc::as_ptr(&arc)) };
+    }
+
+    /// The references that one simulated thread holds.
+    #[derive(Default)]
+    struct Thread {
+        arcs: [Option<Arc<Tracked<'static>, &'static ModelAlloc>>; 2],
+        weaks: [Option<Weak<Tracked<'static>, &'static ModelAlloc>>; 2],
+    }
+
+    /// Two simulated threads sharing one `ArcInner`, with ghost counts of the
+    /// references they hold between them.
+    struct World {
+        model: &'static ModelAlloc,
+        drops: &'static [Cell<u8>],
+        inner: NonNull<ArcInner<Tracked<'static>>>,
+        threads: [RefCell<Thread>; 2],
+        arcs: Cell<usize>,
+        weaks: Cell<usize>,
+        /// The thread whose operation the other one may preempt, if any.
+        running: Cell<Option<usize>>,
+        /// How many more operations may preempt another one.
+        preemptions: Cell<usize>,
+    }
+
+    impl World {
+        /// Runs an arbitrary operation of thread `t` on the references it
+        /// holds, and checks what the operation promises.
+        fn run(&self, t: usize) {
+            let thread = &mut *self.threads[t].borrow_mut();
+            let (i, j) = (usize::from(kani::any::<bool>()), usize::from(kani::any::<bool>()));
+            match kani::any::<u8>() % 6 {
+                0 => {
+                    if thread.arcs[j].is_none() {
+                        if let Some(arc) = &thread.arcs[i] {
+                            thread.arcs[j] = Some(Arc::clone(arc));
+                            self.arcs.set(self.arcs.get() + 1);
+                        }
+                    }
+                }
+                1 => {
+                    if let Some(arc) = thread.arcs[i].take() {
+                        self.arcs.set(self.arcs.get() - 1);
+                        drop(arc);
+                    }
+                }
+                2 => {
+                    if thread.weaks[j].is_none() {
+                        if let Some(arc) = &thread.arcs[i] {
+                            // A `downgrade` that finds `weak` locked spins
+                            // until the preempted thread releases the lock, so
+                            // it is left for later.
+                            if arc.inner().weak.load(Relaxed) != usize::MAX {
+                                thread.weaks[j] = Some(Arc::downgrade(arc));
+                                self.weaks.set(self.weaks.get() + 1);
+                            }
+                        }
+                    }
+                }
+                3 => {
+                    if thread.arcs[j].is_none() {
+                        if let Some(weak) = &thread.weaks[i] {
+                            let arc = weak.upgrade();
+                            if arc.is_some() {
+                                assert_eq!(self.drops[0].get(), 0);
+                                self.arcs.set(self.arcs.get() + 1);
+                            } else {
+                                assert_eq!(self.arcs.get(), 0);
+                            }
+                            thread.arcs[j] = arc;
+                        }
+                    }
+                }
+                4 => {
+                    if let Some(weak) = thread.weaks[i].take() {
+                        self.weaks.set(self.weaks.get() - 1);
+                        drop(weak);
+                    }
+                }
+                _ => {
+                    if let Some(arc) = &mut thread.arcs[i] {
+                        // `get_mut` hands out a `&mut` only if no other
+                        // thread holds, or can get, a reference of either kind.
+                        if Arc::get_mut(arc).is_some() {
+                            assert_eq!((self.arcs.get(), self.weaks.get()), (1, 0));
+                        }
+                    }
+                }
+            }
+        }
+
+        /// Called before every atomic access: if a thread is running an
+        /// operation, lets the other one run a whole operation of its own first.
+        fn preempt(&self) {
+            let Some(t) = self.running.get() else { return };
+            if self.preemptions.get() > 0 && kani::any() {
+                self.preemptions.set(self.preemptions.get() - 1);
+                self.running.set(None);
+                self.run(1 - t);
+                self.running.set(Some(t));
+            }
+        }
+
+        /// Checks that the counts agree with the ghost counts, and that the
+        /// value and the allocation are alive exactly as long as they should be.
+        fn check_counts(&self) {
+            let (arcs, weaks) = (self.arcs.get(), self.weaks.get());
+            assert_eq!(self.drops[0].get(), u8::from(arcs == 0));
+            assert_eq!(self.model.live(), usize::from(arcs + weaks > 0));
+            if arcs + weaks > 0 {
+                // SAFETY: the allocation is live while a reference is.
+                let inner = unsafe { self.inner.as_ref() };
+                assert_eq!(inner.strong.load(Relaxed), arcs);
+                assert_eq!(inner.weak.load(Relaxed), weaks + usize::from(arcs > 0));
+            }
+        }
+    }
+
+    /// The world `check_two_thread_interleavings` runs, while it runs.
+    static mut WORLD: *const World = ptr::null();
+
+    /// Hands control to the running world, if there is one.
+    fn preempt() {
+        // SAFETY: `WORLD` is only set while the world it points to is alive.
+        if let Some(world) = unsafe { WORLD.as_ref() } {
+            world.preempt();
+        }
+    }
+
+    // The atomic accesses of `Arc` and `Weak` are stubbed with these, each of
+    // them a point where the other thread may run. Kani runs a single thread,
+    // so plain reads and writes of the counts are atomic already.
+
+    fn preempting_load(atomic: &AtomicUsize, _: atomic::Ordering) -> usize {
+        preempt();
+        // SAFETY: no other reference to the count is live.
+        unsafe { *atomic.as_ptr() }
+    }
+
+    fn preempting_store(atomic: &AtomicUsize, val: usize, _: atomic::Ordering) {
+        preempt();
+        // SAFETY: see `preempting_load`.
+        unsafe { *atomic.as_ptr() = val };
+    }
+
+    fn preempting_fetch_add(atomic: &AtomicUsize, val: usize, _: atomic::Ordering) -> usize {
+        preempt();
+        // SAFETY: see `preempting_load`.
+        let count = unsafe { &mut *atomic.as_ptr() };
+        let old = *count;
+        *count = old + val;
+        old
+    }
+
+    fn preempting_fetch_sub(atomic: &AtomicUsize, val: usize, _: atomic::Ordering) -> usize {
+        preempt();
+        // SAFETY: see `preempting_load`.
+        let count = unsafe { &mut *atomic.as_ptr() };
+        let old = *count;
+        *count = old - val;
+        old
+    }
+
+    fn preempting_compare_exchange(
+        atomic: &AtomicUsize,
+        current: usize,
+        new: usize,
+        _: atomic::Ordering,
+        _: atomic::Ordering,
+    ) -> Result<usize, usize> {
+        preempt();
+        // SAFETY: see `preempting_load`.
+        let count = unsafe { &mut *atomic.as_ptr() };
+        if *count == current { Ok(mem::replace(count, new)) } else { Err(*count) }
+    }
+
+    fn preempting_fence(_: atomic::Ordering) {
+        preempt();
+    }
+
+    /// Number of operations the simulated threads start between them.
+    const OPERATIONS: usize = 4;
+
+    /// Number of those operations that may also run in the middle of another.
+    const PREEMPTIONS: usize = 2;
+
+    /// Kani has no threads, so the second of two threads runs inside the
+    /// atomic accesses of the first: the stubs above let it run a whole
+    /// operation of its own before any of them. Every operation is a real
+    /// `Arc::clone`, `Arc::downgrade`, `Weak::upgrade`, `Arc::get_mut` or drop
+    /// of either kind, on references the thread holds, so this covers, among
+    /// others, the `is_unique` lock racing `upgrade`, and an `upgrade` between
+    /// the last `fetch_sub` in `drop` and its `Acquire` fence. An operation is
+    /// only ever split by whole operations of the other thread, never by part
+    /// of one.
+    ///
+    /// Ghost counts of the references the threads hold check that the counts
+    /// agree with them between operations, that `upgrade` fails only once no
+    /// `Arc` is left and never hands out a dropped value, that `get_mut`
+    /// succeeds only when its `Arc` is the only reference of either kind, and
+    /// that the value is dropped, and the allocation freed, exactly once.
+    #[kani::proof]
+    #[kani::unwind(5)]
+    #[kani::stub(AtomicUsize::load, preempting_load)]
+    #[kani::stub(AtomicUsize::store, preempting_store)]
+    #[kani::stub(AtomicUsize::fetch_add, preempting_fetch_add)]
+    #[kani::stub(AtomicUsize::fetch_sub, preempting_fetch_sub)]
+    #[kani::stub(AtomicUsize::compare_exchange, preempting_compare_exchange)]
+    #[kani::stub(AtomicUsize::compare_exchange_weak, preempting_compare_exchange)]
+    #[kani::stub(atomic::fence, preempting_fence)]
+    fn check_two_thread_interleavings() {
+        let model: &'static ModelAlloc = Box::leak(Box::new(ModelAlloc::new()));
+        let drops: &'static [Cell<u8>] = Box::leak(Box::new([Cell::new(0)]));
+        let arc = Arc::new_in(Tracked::new(0, drops), model);
+        let world = World {
+            model,
+            drops,
+            inner: arc.ptr,
+            threads: Default::default(),
+            arcs: Cell::new(1),
+            weaks: Cell::new(0),
+            running: Cell::new(None),
+            preemptions: Cell::new(PREEMPTIONS),
+        };
+        world.threads[0].borrow_mut().arcs[0] = Some(arc);
+
+        // SAFETY: `WORLD` is reset before `world` goes away.
+        unsafe { WORLD = &world };
+        for _ in 0..OPERATIONS {
+            let t = usize::from(kani::any::<bool>());
+            world.running.set(Some(t));
+            world.run(t);
+            world.running.set(None);
+            world.check_counts();
+        }
+        // SAFETY: see above.
+        unsafe { WORLD = ptr::null() };
+
+        let World { threads, .. } = world;
+        drop(threads);
+        assert_eq!(drops[0].get(), 1);
+        model.assert_no_leaks();
+    }
+}
@@ -251,3 +251,74 @@ unsafe impl<T, A: Allocator> TrustedLen for Drain<'_, T, A> {}
 
 #[stable(feature = "fused", since = "1.26.0")]
 impl<T, A: Allocator> FusedIterator for Drain<'_, T, A> {}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::cell::Cell;
+    use core::ub_checks::Invariant;
+
+    use super::*;
+    use crate::alloc::model::{ModelAlloc, PanicOnDrop, assert_drops, assert_ids};
+    use crate::kani;
+    use crate::vec::verify::{MAX_CAP, tracked_vec};
+
+    /// Dropping a `Drain` before it is exhausted goes through the same
+    /// `DropGuard` that runs when the destructor of a drained element panics.
+    /// Whatever was taken from either end, the guard moves the tail back over
+    /// the drained range, and every drained element is dropped exactly once.
+    /// A panicking destructor leaves the guard in the same state, as dropping
+    /// the rest of the drained slice carries on past it; see `alloc::model`.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_drop_part_way() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let end = kani::any_where(|end| *end <= len);
+        let start = kani::any_where(|start| *start <= end);
+        let mut vec = tracked_vec(&drops, len, &model);
+        {
+            let mut drain = vec.drain(start..end);
+            for _ in 0..kani::any_where(|front: &usize| *front <= MAX_CAP) {
+                drop(drain.next());
+            }
+            for _ in 0..kani::any_where(|back: &usize| *back <= MAX_CAP) {
+                drop(drain.next_back());
+            }
+        }
+        let mut kept = [0; MAX_CAP];
+        let kept_len = len - (end - start);
+        for (i, id) in (0..start).chain(end..len).enumerate() {
+            kept[i] = id as u8;
+        }
+        assert!(vec.is_safe());
+        assert_eq!(vec.len(), kept_len);
+        unsafe { assert_ids(vec.as_ptr(), &kept[..kept_len]) };
+        assert_drops(&drops, 0..len as u8, &kept[..kept_len]);
+        drop(vec);
+        assert_drops(&drops, 0..len as u8, &[]);
+        model.assert_no_leaks();
+    }
+
+    /// The destructor of a drained element panics while `Drop` drops what is
+    /// left of the drained range.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    #[kani::should_panic]
+    fn check_drop_panicking() {
+        let model = ModelAlloc::new();
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let end = kani::any_where(|end| *end <= len);
+        let start = kani::any_where(|start| *start < end);
+        let mut vec = Vec::with_capacity_in(len, &model);
+        for id in 0..len as u8 {
+            vec.push(PanicOnDrop(id));
+        }
+        let mut drain = vec.drain(start..end);
+        for _ in 0..kani::any_where(|front: &usize| *front < end - start) {
+            mem::forget(drain.next());
+        }
+        drop(drain);
+    }
+}
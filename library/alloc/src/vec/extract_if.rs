@@ -110,3 +110,77 @@ where
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::cell::Cell;
+    use core::ub_checks::Invariant;
+
+    use super::*;
+    use crate::alloc::model::{ModelAlloc, assert_drops, assert_ids};
+    use crate::kani;
+    use crate::vec::verify::{MAX_CAP, tracked_vec};
+
+    /// `idx` only moves past an element once the predicate has returned, so
+    /// dropping an `ExtractIf` part-way leaves it in the same state as a panic
+    /// in the predicate would. Either way `Drop` must backshift the unprocessed
+    /// elements over the gap left by the extracted ones; see `alloc::model`.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_drop_part_way() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let mut vec = tracked_vec(&drops, len, &model);
+        let mut kept = [0; MAX_CAP];
+        let mut kept_len = 0;
+        let mut processed = 0;
+        {
+            let mut iter = vec.extract_if(|elem| {
+                processed += 1;
+                let extract = kani::any();
+                if !extract {
+                    kept[kept_len] = elem.id();
+                    kept_len += 1;
+                }
+                extract
+            });
+            for _ in 0..kani::any_where(|steps: &usize| *steps <= MAX_CAP) {
+                drop(iter.next());
+            }
+        }
+        let extracted = processed - kept_len;
+        for id in processed..len {
+            kept[kept_len] = id as u8;
+            kept_len += 1;
+        }
+        assert!(vec.is_safe());
+        assert_eq!(vec.len(), len - extracted);
+        unsafe { assert_ids(vec.as_ptr(), &kept[..kept_len]) };
+        assert_drops(&drops, 0..len as u8, &kept[..kept_len]);
+        drop(vec);
+        assert_drops(&drops, 0..len as u8, &[]);
+        model.assert_no_leaks();
+    }
+
+    /// The predicate panics on an arbitrary element, with the elements before
+    /// it already extracted or kept.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    #[kani::should_panic]
+    fn check_predicate_panicking() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let panics_at = kani::any_where(|id: &u8| usize::from(*id) < len);
+        let mut vec = tracked_vec(&drops, len, &model);
+        vec.extract_if(|elem| {
+            if elem.id() == panics_at {
+                panic!("the predicate panicked");
+            }
+            kani::any()
+        })
+        .for_each(drop);
+    }
+}
@@ -162,9 +162,23 @@ use core::mem::{self, ManuallyDrop, SizedTypeProperties};
 use core::num::NonZero;
 use core::ptr;
 
+use safety::{ensures, requires};
+
 use super::{InPlaceDrop, InPlaceDstDataSrcBufDrop, SpecFromIter, SpecFromIterNested, Vec};
 use crate::alloc::{Global, handle_alloc_error};
 
+#[ensures(|result| !*result || (
+    !SRC::IS_ZST
+        && !DEST::IS_ZST
+        && mem::align_of::<SRC>() == mem::align_of::<DEST>()
+        && step_merge.zip(step_expand).is_some_and(|(merge, expand)| {
+            mem::size_of::<SRC>().checked_mul(merge.get()).is_some_and(|src_size| {
+                mem::size_of::<DEST>()
+                    .checked_mul(expand.get())
+                    .is_some_and(|dest_size| dest_size <= src_size)
+            })
+        })
+))]
 const fn in_place_collectible<DEST, SRC>(
     step_merge: Option<NonZero<usize>>,
     step_expand: Option<NonZero<usize>>,
@@ -189,6 +203,13 @@ const fn in_place_collectible<DEST, SRC>(
     }
 }
 
+#[requires(mem::align_of::<SRC>() == mem::align_of::<DEST>() && !DEST::IS_ZST)]
+#[requires(src_cap.checked_mul(mem::size_of::<SRC>()).is_some_and(|src_size| {
+    src_size <= isize::MAX as usize && dst_cap == src_size / mem::size_of::<DEST>()
+}))]
+#[ensures(|result| *result == (
+    src_cap * mem::size_of::<SRC>() != dst_cap * mem::size_of::<DEST>()
+))]
 const fn needs_realloc<SRC, DEST>(src_cap: usize, dst_cap: usize) -> bool {
     if const { mem::align_of::<SRC>() != mem::align_of::<DEST>() } {
         // FIXME(const-hack): use unreachable! once that works in const
@@ -429,3 +450,189 @@ pub(crate) unsafe trait AsVecIntoIter {
     type Item;
     fn as_into_iter(&mut self) -> &mut super::IntoIter<Self::Item>;
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::cell::Cell;
+    use core::ptr::NonNull;
+    use core::ub_checks::Invariant;
+
+    use super::*;
+    use crate::alloc::model::{PanicOnDrop, Tracked, assert_drops};
+    use crate::kani;
+
+    /// Largest capacity of the source vectors.
+    const MAX_CAP: usize = 4;
+
+    fn any_step() -> Option<NonZero<usize>> {
+        NonZero::new(kani::any_where(|step| *step <= MAX_CAP))
+    }
+
+    /// Collects a `map`/`filter` chain over a vector of `make(0)..make(len)`.
+    ///
+    /// Before each source item is mapped, `map`'s closure checks that it and
+    /// every item after it still hold their original values, so no destination
+    /// item written so far has overrun an unread source item. Afterwards, when
+    /// the source buffer was reused, the destination capacity must cover as
+    /// much of it as possible, and without a reallocation the buffer must be
+    /// the same one with the same size in bytes, so that dropping the result
+    /// deallocates it with the layout it was allocated with.
+    fn check_collect<SRC: Copy + PartialEq, DEST>(make: fn(u8) -> SRC, map: fn(SRC) -> DEST) {
+        let cap = kani::any_where(|cap| *cap <= MAX_CAP);
+        let len = kani::any_where(|len| *len <= cap);
+        let mut src = Vec::with_capacity(cap);
+        for i in 0..len as u8 {
+            src.push(make(i));
+        }
+        let src_buf = src.as_ptr();
+        let src_cap = src.capacity();
+
+        let mut read = 0;
+        let mut kept = 0;
+        let dst: Vec<DEST> = src
+            .into_iter()
+            .map(|item| {
+                assert!(item == make(read as u8));
+                read += 1;
+                for i in read..len {
+                    assert!(unsafe { ptr::read(src_buf.add(i)) } == make(i as u8));
+                }
+                map(item)
+            })
+            .filter(|_| {
+                let keep = kani::any();
+                kept += usize::from(keep);
+                keep
+            })
+            .collect();
+        assert_eq!(read, len);
+        assert!(dst.is_safe());
+        assert_eq!(dst.len(), kept);
+
+        if in_place_collectible::<DEST, SRC>(NonZero::new(1), NonZero::new(1)) {
+            let src_size = src_cap * mem::size_of::<SRC>();
+            assert_eq!(dst.capacity(), src_size / mem::size_of::<DEST>());
+            if !needs_realloc::<SRC, DEST>(src_cap, dst.capacity()) {
+                assert_eq!(dst.as_ptr().cast::<SRC>(), src_buf);
+                assert_eq!(dst.capacity() * mem::size_of::<DEST>(), src_size);
+            }
+        }
+    }
+
+    macro_rules! check_in_place_collect {
+        ($($name:ident: $src:ty => $dest:ty, $make:expr, $map:expr;)*) => {$(
+            mod $name {
+                use super::*;
+
+                #[kani::proof_for_contract(in_place_collectible::<$dest, $src>)]
+                fn check_in_place_collectible() {
+                    let _ = in_place_collectible::<$dest, $src>(any_step(), any_step());
+                }
+
+                #[kani::proof_for_contract(needs_realloc::<$src, $dest>)]
+                fn check_needs_realloc() {
+                    let _ = needs_realloc::<$src, $dest>(kani::any(), kani::any());
+                }
+
+                #[kani::proof]
+                #[kani::unwind(6)]
+                fn check_collect() {
+                    super::check_collect::<$src, $dest>($make, $map);
+                }
+            }
+        )*};
+    }
+
+    check_in_place_collect! {
+        same_size: u32 => u32, |i| u32::from(i), |x| x ^ 1;
+        halved: [u16; 2] => u16, |i| [u16::from(i); 2], |[a, b]| a ^ b;
+        two_thirds: [u8; 3] => [u8; 2], |i| [i; 3], |[a, b, c]| [a ^ b, c];
+        two_thirds_aligned: [u32; 3] => [u32; 2], |i| [u32::from(i); 3], |[a, _, c]| [a, c];
+    }
+
+    /// `Zip` collects in place into the buffer of its first iterator, reading
+    /// the second one alongside it.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_collect_zip() {
+        let cap = kani::any_where(|cap| *cap <= MAX_CAP);
+        let len = kani::any_where(|len| *len <= cap);
+        let mut src = Vec::with_capacity(cap);
+        for i in 0..len as u32 {
+            src.push(i);
+        }
+        let src_buf = src.as_ptr();
+        let other: [u32; MAX_CAP] = kani::any();
+
+        let dst: Vec<u32> = src.into_iter().zip(other).map(|(a, b)| a ^ b).collect();
+        assert!(dst.is_safe());
+        assert_eq!(dst.len(), len);
+        for i in 0..len {
+            assert_eq!(dst[i], i as u32 ^ other[i]);
+        }
+        assert_eq!(dst.as_ptr(), src_buf);
+        assert_eq!(dst.capacity(), cap);
+    }
+
+    /// Once every item has been mapped, `from_iter_in_place` drops the unread
+    /// source items under an `InPlaceDstDataSrcBufDrop`, so that a panicking
+    /// destructor still drops the destination items and frees the source
+    /// buffer. This drops the guard in the state it is armed in; see
+    /// `alloc::model`. The destination items are smaller than the source ones,
+    /// so freeing the buffer with any layout other than the one it was
+    /// allocated with fails Kani's check that deallocations match their
+    /// allocation size.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_dst_guard_frees_src_buf() {
+        const MAX_LEN: usize = MAX_CAP * 4 / 3;
+        type Src = [u64; 4];
+
+        let drops = [const { Cell::new(0) }; MAX_LEN];
+        let src_cap = kani::any_where(|cap| *cap <= MAX_CAP);
+        let dst_cap = src_cap * mem::size_of::<Src>() / mem::size_of::<Tracked<'_>>();
+        let len = kani::any_where(|len| *len <= dst_cap);
+        let mut src = ManuallyDrop::new(Vec::<Src>::with_capacity(src_cap));
+        let dst = NonNull::new(src.as_mut_ptr()).unwrap().cast::<Tracked<'_>>();
+        for i in 0..len {
+            // SAFETY: `dst_cap` items of `Tracked` fit into the source buffer.
+            unsafe { dst.add(i).write(Tracked::new(i as u8, &drops)) };
+        }
+
+        drop(InPlaceDstDataSrcBufDrop { ptr: dst, len, src_cap, src: PhantomData::<Src> });
+        assert_drops(&drops, 0..len as u8, &[]);
+    }
+
+    /// A source item that was never read panics when `from_iter_in_place`
+    /// drops it under the armed `InPlaceDstDataSrcBufDrop`.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    #[kani::should_panic]
+    fn check_collect_drop_panicking() {
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let taken = kani::any_where(|taken| *taken < len);
+        let mut src = Vec::with_capacity(len);
+        for id in 0..len as u8 {
+            src.push(PanicOnDrop(id));
+        }
+        let dst: Vec<u8> = src
+            .into_iter()
+            .take(taken)
+            .map(|item| {
+                let id = item.0;
+                mem::forget(item);
+                id
+            })
+            .collect();
+        drop(dst);
+    }
+
+    /// A change of alignment falls back to allocating a new buffer, which
+    /// must not touch the source items either.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_collect_alignment_change() {
+        check_collect::<u16, u8>(|i| u16::from(i), |x| x as u8);
+    }
+}
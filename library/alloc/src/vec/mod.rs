@@ -65,6 +65,10 @@ use core::mem::{self, ManuallyDrop, MaybeUninit, SizedTypeProperties};
 use core::ops::{self, Index, IndexMut, Range, RangeBounds};
 use core::ptr::{self, NonNull};
 use core::slice::{self, SliceIndex};
+#[allow(unused_imports)]
+use core::ub_checks::{self, Invariant};
+
+use safety::{ensures, requires};
 
 #[unstable(feature = "extract_if", reason = "recently added", issue = "43244")]
 pub use self::extract_if::ExtractIf;
@@ -72,6 +76,8 @@ use crate::alloc::{Allocator, Global};
 use crate::borrow::{Cow, ToOwned};
 use crate::boxed::Box;
 use crate::collections::TryReserveError;
+#[cfg(kani)]
+use crate::kani;
 use crate::raw_vec::RawVec;
 
 mod extract_if;
@@ -602,6 +608,13 @@ impl<T> Vec<T> {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[requires(!ptr.is_null() && ptr.is_aligned())]
+    #[requires(T::IS_ZST
+        || (length <= capacity && capacity <= isize::MAX as usize / size_of::<T>()))]
+    #[requires(T::IS_ZST || capacity == 0
+        || ub_checks::can_write(ptr::slice_from_raw_parts_mut(ptr, capacity)))]
+    #[requires(ub_checks::can_dereference(ptr::slice_from_raw_parts(ptr, length)))]
+    #[ensures(|vec: &Self| vec.is_safe() && vec.len == length && vec.as_ptr() == ptr)]
     pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> Self {
         unsafe { Self::from_raw_parts_in(ptr, length, capacity, Global) }
     }
@@ -714,6 +727,13 @@ impl<T> Vec<T> {
     /// ```
     #[inline]
     #[unstable(feature = "box_vec_non_null", reason = "new API", issue = "130364")]
+    #[requires(ptr.is_aligned())]
+    #[requires(T::IS_ZST
+        || (length <= capacity && capacity <= isize::MAX as usize / size_of::<T>()))]
+    #[requires(T::IS_ZST || capacity == 0
+        || ub_checks::can_write(NonNull::slice_from_raw_parts(ptr, capacity).as_ptr()))]
+    #[requires(ub_checks::can_dereference(NonNull::slice_from_raw_parts(ptr, length).as_ptr()))]
+    #[ensures(|vec: &Self| vec.is_safe() && vec.len == length && vec.as_ptr() == ptr.as_ptr())]
     pub unsafe fn from_parts(ptr: NonNull<T>, length: usize, capacity: usize) -> Self {
         unsafe { Self::from_parts_in(ptr, length, capacity, Global) }
     }
@@ -931,6 +951,13 @@ impl<T, A: Allocator> Vec<T, A> {
     /// ```
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
+    #[requires(!ptr.is_null() && ptr.is_aligned())]
+    #[requires(T::IS_ZST
+        || (length <= capacity && capacity <= isize::MAX as usize / size_of::<T>()))]
+    #[requires(T::IS_ZST || capacity == 0
+        || ub_checks::can_write(ptr::slice_from_raw_parts_mut(ptr, capacity)))]
+    #[requires(ub_checks::can_dereference(ptr::slice_from_raw_parts(ptr, length)))]
+    #[ensures(|vec: &Self| vec.is_safe() && vec.len == length && vec.as_ptr() == ptr)]
     pub unsafe fn from_raw_parts_in(ptr: *mut T, length: usize, capacity: usize, alloc: A) -> Self {
         unsafe { Vec { buf: RawVec::from_raw_parts_in(ptr, capacity, alloc), len: length } }
     }
@@ -1047,6 +1074,13 @@ impl<T, A: Allocator> Vec<T, A> {
     #[inline]
     #[unstable(feature = "allocator_api", reason = "new API", issue = "32838")]
     // #[unstable(feature = "box_vec_non_null", issue = "130364")]
+    #[requires(ptr.is_aligned())]
+    #[requires(T::IS_ZST
+        || (length <= capacity && capacity <= isize::MAX as usize / size_of::<T>()))]
+    #[requires(T::IS_ZST || capacity == 0
+        || ub_checks::can_write(NonNull::slice_from_raw_parts(ptr, capacity).as_ptr()))]
+    #[requires(ub_checks::can_dereference(NonNull::slice_from_raw_parts(ptr, length).as_ptr()))]
+    #[ensures(|vec: &Self| vec.is_safe() && vec.len == length && vec.as_ptr() == ptr.as_ptr())]
     pub unsafe fn from_parts_in(ptr: NonNull<T>, length: usize, capacity: usize, alloc: A) -> Self {
         unsafe { Vec { buf: RawVec::from_nonnull_in(ptr, capacity, alloc), len: length } }
     }
@@ -1520,6 +1554,9 @@ impl<T, A: Allocator> Vec<T, A> {
     /// [`clear`]: Vec::clear
     /// [`drain`]: Vec::drain
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[requires(self.is_safe())]
+    #[ensures(|_| self.is_safe() && self.len == old(self.len.min(len)))]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     pub fn truncate(&mut self, len: usize) {
         // This is safe because:
         //
@@ -1884,6 +1921,9 @@ impl<T, A: Allocator> Vec<T, A> {
     /// the contents and thus not leak memory.
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[requires(new_len <= self.capacity())]
+    #[ensures(|_| self.len == new_len)]
+    #[cfg_attr(kani, kani::modifies(&self.len))]
     pub unsafe fn set_len(&mut self, new_len: usize) {
         debug_assert!(new_len <= self.capacity());
 
@@ -1916,6 +1956,9 @@ impl<T, A: Allocator> Vec<T, A> {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[requires(self.is_safe())]
+    #[ensures(|_| self.is_safe() && self.len == old(self.len) - 1)]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     pub fn swap_remove(&mut self, index: usize) -> T {
         #[cold]
         #[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
@@ -1966,6 +2009,9 @@ impl<T, A: Allocator> Vec<T, A> {
     #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[track_caller]
+    #[requires(self.is_safe())]
+    #[ensures(|_| self.is_safe() && self.len == old(self.len) + 1)]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     pub fn insert(&mut self, index: usize, element: T) {
         #[cold]
         #[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
@@ -2029,6 +2075,9 @@ impl<T, A: Allocator> Vec<T, A> {
     #[stable(feature = "rust1", since = "1.0.0")]
     #[track_caller]
     #[rustc_confusables("delete", "take")]
+    #[requires(self.is_safe())]
+    #[ensures(|_| self.is_safe() && self.len == old(self.len) - 1)]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     pub fn remove(&mut self, index: usize) -> T {
         #[cold]
         #[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
@@ -2085,6 +2134,9 @@ impl<T, A: Allocator> Vec<T, A> {
     /// assert_eq!(vec, [2, 3, 5]);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[requires(self.is_safe())]
+    #[ensures(|_| self.is_safe() && self.len <= old(self.len))]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&T) -> bool,
@@ -2111,6 +2163,9 @@ impl<T, A: Allocator> Vec<T, A> {
     /// assert_eq!(vec, [2, 3, 4]);
     /// ```
     #[stable(feature = "vec_retain_mut", since = "1.61.0")]
+    #[requires(self.is_safe())]
+    #[ensures(|_| self.is_safe() && self.len <= old(self.len))]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     pub fn retain_mut<F>(&mut self, mut f: F)
     where
         F: FnMut(&mut T) -> bool,
@@ -2406,6 +2461,9 @@ impl<T, A: Allocator> Vec<T, A> {
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_confusables("push_back", "put", "append")]
     #[track_caller]
+    #[requires(self.is_safe())]
+    #[ensures(|_| self.is_safe() && self.len == old(self.len) + 1)]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     pub fn push(&mut self, value: T) {
         // Inform codegen that the length does not change across grow_one().
         let len = self.len;
@@ -2492,6 +2550,10 @@ impl<T, A: Allocator> Vec<T, A> {
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[cfg_attr(not(test), rustc_diagnostic_item = "vec_pop")]
+    #[requires(self.is_safe())]
+    #[ensures(|popped| self.is_safe() && popped.is_some() == (old(self.len) > 0))]
+    #[ensures(|_| self.len == old(self.len.saturating_sub(1)))]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     pub fn pop(&mut self) -> Option<T> {
         if self.len == 0 {
             None
@@ -2861,6 +2923,8 @@ impl<T, A: Allocator> Vec<T, A> {
     /// ```
     #[stable(feature = "vec_spare_capacity", since = "1.60.0")]
     #[inline]
+    #[requires(self.is_safe())]
+    #[ensures(|spare: &&mut [MaybeUninit<T>]| spare.len() == old(self.capacity() - self.len))]
     pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
         // Note:
         // This method is not implemented in terms of `split_at_spare_mut`,
@@ -2926,6 +2990,9 @@ impl<T, A: Allocator> Vec<T, A> {
     /// ```
     #[unstable(feature = "vec_split_at_spare", issue = "81944")]
     #[inline]
+    #[requires(self.is_safe())]
+    #[ensures(|(init, spare): &(&mut [T], &mut [MaybeUninit<T>])| init.len() == old(self.len)
+        && spare.len() == old(self.capacity() - self.len))]
     pub fn split_at_spare_mut(&mut self) -> (&mut [T], &mut [MaybeUninit<T>]) {
         // SAFETY:
         // - len is ignored and so never changed
@@ -2936,6 +3003,7 @@ impl<T, A: Allocator> Vec<T, A> {
     /// Safety: changing returned .2 (&mut usize) is considered the same as calling `.set_len(_)`.
     ///
     /// This method provides unique access to all vec parts at once in `extend_from_within`.
+    #[requires(self.is_safe())]
     unsafe fn split_at_spare_mut_with_len(
         &mut self,
     ) -> (&mut [T], &mut [MaybeUninit<T>], &mut usize) {
@@ -2958,6 +3026,12 @@ impl<T, A: Allocator> Vec<T, A> {
             (initialized, spare, &mut self.len)
         }
     }
+
+    /// The whole buffer, as a target for `kani::modifies` clauses.
+    #[cfg(kani)]
+    pub(crate) fn allocation(&self) -> *mut [T] {
+        ptr::slice_from_raw_parts_mut(self.buf.ptr(), self.buf.capacity())
+    }
 }
 
 impl<T: Clone, A: Allocator> Vec<T, A> {
@@ -3047,6 +3121,10 @@ impl<T: Clone, A: Allocator> Vec<T, A> {
     #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "vec_extend_from_within", since = "1.53.0")]
     #[track_caller]
+    #[requires(self.is_safe())]
+    #[ensures(|_| self.is_safe() && self.len == old(self.len
+        + slice::range((src.start_bound().cloned(), src.end_bound().cloned()), ..self.len).len()))]
+    #[cfg_attr(kani, kani::modifies(self, self.allocation()))]
     pub fn extend_from_within<R>(&mut self, src: R)
     where
         R: RangeBounds<usize>,
@@ -3739,6 +3817,16 @@ impl<T: Ord, A: Allocator> Ord for Vec<T, A> {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T, A: Allocator> Invariant for Vec<T, A> {
+    /// The buffer is a valid `RawVec` and the length fits in it. That the
+    /// first `len` elements are initialized is left to the harnesses, which
+    /// track it through drop counts and element values.
+    fn is_safe(&self) -> bool {
+        self.buf.is_safe() && self.len <= self.buf.capacity()
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 unsafe impl<#[may_dangle] T, A: Allocator> Drop for Vec<T, A> {
     fn drop(&mut self) {
@@ -4030,3 +4118,361 @@ impl<T, A: Allocator, const N: usize> TryFrom<Vec<T, A>> for [T; N] {
         Ok(array)
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::cell::Cell;
+
+    use super::*;
+    use crate::alloc::model::{ModelAlloc, Tracked, assert_drops, assert_ids};
+
+    /// Largest capacity of the vectors built by the harnesses.
+    pub(super) const MAX_CAP: usize = 4;
+
+    /// A vector with up to `MAX_CAP` capacity holding arbitrary elements.
+    fn any_vec<T: kani::Arbitrary, A: Allocator>(alloc: A) -> Vec<T, A> {
+        let cap = kani::any_where(|cap| *cap <= MAX_CAP);
+        let len = kani::any_where(|len| *len <= cap);
+        let mut vec = Vec::with_capacity_in(cap, alloc);
+        for _ in 0..len {
+            vec.push(kani::any());
+        }
+        vec
+    }
+
+    /// A vector of `len` tracked elements with ids `0..len`.
+    pub(super) fn tracked_vec<'a, A: Allocator>(
+        drops: &'a [Cell<u8>],
+        len: usize,
+        alloc: A,
+    ) -> Vec<Tracked<'a>, A> {
+        let mut vec = Vec::with_capacity_in(len, alloc);
+        for id in 0..len as u8 {
+            vec.push(Tracked::new(id, drops));
+        }
+        vec
+    }
+
+    macro_rules! check_vec {
+        ($($name:ident: $ty:ty,)*) => {$(
+            mod $name {
+                use super::*;
+
+                #[kani::proof_for_contract(Vec::<$ty>::from_raw_parts)]
+                #[kani::unwind(5)]
+                fn check_from_raw_parts() {
+                    let mut vec = ManuallyDrop::new(any_vec::<$ty, _>(Global));
+                    let (ptr, len, cap) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+                    let _ = unsafe { Vec::from_raw_parts(ptr, len, cap) };
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty>::from_parts)]
+                #[kani::unwind(5)]
+                fn check_from_parts() {
+                    let mut vec = ManuallyDrop::new(any_vec::<$ty, _>(Global));
+                    let ptr = NonNull::new(vec.as_mut_ptr()).unwrap();
+                    let _ = unsafe { Vec::from_parts(ptr, vec.len(), vec.capacity()) };
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::from_raw_parts_in)]
+                #[kani::unwind(5)]
+                fn check_from_raw_parts_in() {
+                    let mut vec = ManuallyDrop::new(any_vec::<$ty, _>(ModelAlloc::new()));
+                    let alloc = unsafe { ptr::read(vec.allocator()) };
+                    let (ptr, len, cap) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+                    let _ = unsafe { Vec::from_raw_parts_in(ptr, len, cap, alloc) };
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::from_parts_in)]
+                #[kani::unwind(5)]
+                fn check_from_parts_in() {
+                    let mut vec = ManuallyDrop::new(any_vec::<$ty, _>(ModelAlloc::new()));
+                    let alloc = unsafe { ptr::read(vec.allocator()) };
+                    let ptr = NonNull::new(vec.as_mut_ptr()).unwrap();
+                    let _ = unsafe { Vec::from_parts_in(ptr, vec.len(), vec.capacity(), alloc) };
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::set_len)]
+                #[kani::unwind(5)]
+                fn check_set_len() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    let new_len = kani::any_where(|len| *len <= vec.len());
+                    unsafe { vec.set_len(new_len) };
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::push)]
+                #[kani::unwind(5)]
+                fn check_push() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    vec.push(kani::any());
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::pop)]
+                #[kani::unwind(5)]
+                fn check_pop() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    let _ = vec.pop();
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::insert)]
+                #[kani::unwind(5)]
+                fn check_insert() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    let index = kani::any_where(|index| *index <= vec.len());
+                    vec.insert(index, kani::any());
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::remove)]
+                #[kani::unwind(5)]
+                fn check_remove() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    let index = kani::any_where(|index| *index < vec.len());
+                    let _ = vec.remove(index);
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::swap_remove)]
+                #[kani::unwind(5)]
+                fn check_swap_remove() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    let index = kani::any_where(|index| *index < vec.len());
+                    let _ = vec.swap_remove(index);
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::truncate)]
+                #[kani::unwind(5)]
+                fn check_truncate() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    vec.truncate(kani::any_where(|len| *len <= MAX_CAP + 1));
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::extend_from_within)]
+                #[kani::unwind(9)]
+                fn check_extend_from_within() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    let end = kani::any_where(|end| *end <= vec.len());
+                    let start = kani::any_where(|start| *start <= end);
+                    vec.extend_from_within(start..end);
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::retain)]
+                #[kani::unwind(5)]
+                fn check_retain() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    vec.retain(|_| kani::any());
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::spare_capacity_mut)]
+                #[kani::unwind(5)]
+                fn check_spare_capacity_mut() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    let _ = vec.spare_capacity_mut();
+                }
+
+                #[kani::proof_for_contract(Vec::<$ty, ModelAlloc>::split_at_spare_mut)]
+                #[kani::unwind(5)]
+                fn check_split_at_spare_mut() {
+                    let mut vec = any_vec::<$ty, _>(ModelAlloc::new());
+                    let _ = vec.split_at_spare_mut();
+                }
+            }
+        )*};
+    }
+
+    check_vec! {
+        u8s: u8,
+        u32s: u32,
+        units: (),
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_push_pop_round_trip() {
+        let model = ModelAlloc::new();
+        let mut vec = any_vec::<u8, _>(&model);
+        let original = vec.clone();
+        let value = kani::any();
+        vec.push(value);
+        assert_eq!(vec.last(), Some(&value));
+        assert_eq!(vec.pop(), Some(value));
+        assert_eq!(vec, original);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_insert_remove_round_trip() {
+        let model = ModelAlloc::new();
+        let mut vec = any_vec::<u8, _>(&model);
+        let original = vec.clone();
+        let index = kani::any_where(|index| *index <= vec.len());
+        let value = kani::any();
+        vec.insert(index, value);
+        assert_eq!(vec[index], value);
+        assert_eq!(vec.remove(index), value);
+        assert_eq!(vec, original);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_swap_remove_moves_last() {
+        let model = ModelAlloc::new();
+        let mut vec = any_vec::<u8, _>(&model);
+        let original = vec.clone();
+        let index = kani::any_where(|index| *index < vec.len());
+        assert_eq!(vec.swap_remove(index), original[index]);
+        if index < vec.len() {
+            assert_eq!(vec[index], original[original.len() - 1]);
+        }
+        assert_eq!(vec[..index], original[..index]);
+    }
+
+    /// Returns the index of `elem` in the buffer starting at address `start`.
+    ///
+    /// Only addresses are compared, so this does not read the buffer while the
+    /// vector is mutably borrowed.
+    fn index_of(elem: &Tracked<'_>, start: usize) -> usize {
+        ((elem as *const Tracked<'_>).addr() - start) / mem::size_of::<Tracked<'_>>()
+    }
+
+    /// If the predicate (or the destructor of a rejected element) panics,
+    /// `BackshiftOnDrop` moves the unprocessed elements down over the gap and
+    /// sets the length, which leaves the vector as a predicate that keeps every
+    /// remaining element would, so this harness checks every such outcome once
+    /// `retain_mut` returns; see `alloc::model`. While it runs, the vector
+    /// is mutably borrowed, so the predicate only checks what it is handed and
+    /// the drop counters: each element comes in order and in place, and each
+    /// rejected element has been dropped exactly once, with nothing else
+    /// dropped.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_retain_guard_state() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let mut vec = tracked_vec(&drops, len, &model);
+        let start = vec.as_ptr().addr();
+        let mut kept = [0; MAX_CAP];
+        let mut kept_len = 0;
+        vec.retain_mut(|elem| {
+            let processed = index_of(elem, start);
+            assert_eq!(usize::from(elem.id()), processed);
+            assert_drops(&drops, 0..processed as u8, &kept[..kept_len]);
+            assert!(drops[processed..len].iter().all(|drops| drops.get() == 0));
+
+            let keep = kani::any();
+            if keep {
+                kept[kept_len] = elem.id();
+                kept_len += 1;
+            }
+            keep
+        });
+        assert!(vec.is_safe());
+        assert_eq!(vec.len(), kept_len);
+        unsafe { assert_ids(vec.as_ptr(), &kept[..kept_len]) };
+        assert_drops(&drops, 0..len as u8, &kept[..kept_len]);
+        drop(vec);
+        assert_drops(&drops, 0..len as u8, &[]);
+        model.assert_no_leaks();
+    }
+
+    /// The predicate panics on an arbitrary element while `BackshiftOnDrop` is
+    /// armed.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    #[kani::should_panic]
+    fn check_retain_panicking() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let panics_at = kani::any_where(|id: &u8| usize::from(*id) < len);
+        let mut vec = tracked_vec(&drops, len, &model);
+        vec.retain_mut(|elem| {
+            if elem.id() == panics_at {
+                panic!("the predicate panicked");
+            }
+            kani::any()
+        });
+    }
+
+    /// The same argument for `dedup_by` and `FillGapOnDrop`, which on a panic
+    /// in `same_bucket` keeps every unprocessed element as if it had answered
+    /// `false`. Whenever `same_bucket` is called, `prev` is the last distinct
+    /// element found so far, right in front of the gap, `current` is untouched
+    /// in place, and the duplicates that were found have been dropped exactly
+    /// once.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_dedup_by_guard_state() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let mut vec = tracked_vec(&drops, len, &model);
+        let start = vec.as_ptr().addr();
+        let mut kept = [0; MAX_CAP];
+        let mut kept_len = len.min(1);
+        vec.dedup_by(|current, prev| {
+            let read = index_of(current, start);
+            let write = index_of(prev, start) + 1;
+            assert!(write <= read);
+            assert_eq!(write, kept_len);
+            assert_eq!(prev.id(), kept[write - 1]);
+            assert_eq!(usize::from(current.id()), read);
+            assert_drops(&drops, 0..read as u8, &kept[..kept_len]);
+            assert!(drops[read..len].iter().all(|drops| drops.get() == 0));
+
+            let duplicate = kani::any();
+            if !duplicate {
+                kept[kept_len] = current.id();
+                kept_len += 1;
+            }
+            duplicate
+        });
+        assert!(vec.is_safe());
+        assert_eq!(vec.len(), kept_len);
+        unsafe { assert_ids(vec.as_ptr(), &kept[..kept_len]) };
+        assert_drops(&drops, 0..len as u8, &kept[..kept_len]);
+        drop(vec);
+        assert_drops(&drops, 0..len as u8, &[]);
+        model.assert_no_leaks();
+    }
+
+    /// `same_bucket` panics on an arbitrary element while `FillGapOnDrop` is
+    /// armed.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    #[kani::should_panic]
+    fn check_dedup_by_panicking() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP];
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let panics_at = kani::any_where(|id: &u8| *id > 0 && usize::from(*id) < len);
+        let mut vec = tracked_vec(&drops, len, &model);
+        vec.dedup_by(|current, _| {
+            if current.id() == panics_at {
+                panic!("`same_bucket` panicked");
+            }
+            kani::any()
+        });
+    }
+
+    /// Arbitrary sequences of the methods above never leak the buffer and
+    /// free it with the layout it was allocated with.
+    #[kani::proof]
+    #[kani::unwind(9)]
+    fn check_no_leaks() {
+        let model = ModelAlloc::new();
+        let mut vec = any_vec::<u8, _>(&model);
+        for _ in 0..3 {
+            match kani::any::<u8>() % 5 {
+                0 => vec.push(kani::any()),
+                1 => drop(vec.pop()),
+                2 => vec.truncate(kani::any_where(|len| *len <= MAX_CAP)),
+                3 => vec.retain(|_| kani::any()),
+                _ => vec.shrink_to_fit(),
+            }
+            assert!(vec.is_safe());
+        }
+        drop(vec);
+        model.assert_no_leaks();
+    }
+}
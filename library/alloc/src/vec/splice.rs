@@ -139,3 +139,133 @@ impl<T, A: Allocator> Drain<'_, T, A> {
         self.tail_start = new_tail_start;
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use core::cell::Cell;
+    use core::ops::Range;
+    use core::ub_checks::Invariant;
+
+    use super::*;
+    use crate::alloc::model::{ModelAlloc, Tracked, assert_drops, assert_ids};
+    use crate::kani;
+    use crate::vec::verify::{MAX_CAP, tracked_vec};
+
+    /// Largest number of elements spliced in.
+    const MAX_REPLACE: u8 = 2;
+
+    /// Yields tracked elements with ids `first..end` and reports an arbitrary,
+    /// possibly wrong, lower bound.
+    ///
+    /// Before yielding it checks through the drop counters that the vector
+    /// being spliced into is in a state `Drain::drop` can restore, should
+    /// `next` panic instead: the drained elements have been dropped exactly
+    /// once, and nothing else has been. The vector itself is mutably borrowed
+    /// by the splice at that point, so its contents are only checked after.
+    struct Replacement<'a> {
+        drops: &'a [Cell<u8>],
+        len: usize,
+        drained: Range<usize>,
+        first: u8,
+        next: u8,
+        end: u8,
+        lower_bound: usize,
+    }
+
+    impl<'a> Iterator for Replacement<'a> {
+        type Item = Tracked<'a>;
+
+        fn next(&mut self) -> Option<Tracked<'a>> {
+            let untouched =
+                |ids: Range<usize>| self.drops[ids].iter().all(|drops| drops.get() == 0);
+            assert_drops(self.drops, self.drained.start as u8..self.drained.end as u8, &[]);
+            assert!(untouched(0..self.drained.start) && untouched(self.drained.end..self.len));
+            assert!(untouched(usize::from(self.first)..usize::from(self.next)));
+            if self.next == self.end {
+                return None;
+            }
+            self.next += 1;
+            Some(Tracked::new(self.next - 1, self.drops))
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.lower_bound, None)
+        }
+    }
+
+    /// Splicing in any number of elements, with any size hint, leaves the
+    /// vector holding exactly the head, the new elements and the tail, and the
+    /// replacement iterator only ever runs while `Drain::drop` could still
+    /// restore the vector if it panicked.
+    ///
+    /// A panic in `next` leaves the same state as the iterator ending there:
+    /// either way `Drain::drop` moves the tail back behind the elements spliced
+    /// in so far, and those collected but not yet spliced in are dropped by
+    /// their temporary vector; see `alloc::model`.
+    #[kani::proof]
+    #[kani::unwind(8)]
+    fn check_splice_restorable() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP + MAX_REPLACE as usize];
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let end = kani::any_where(|end| *end <= len);
+        let start = kani::any_where(|start| *start <= end);
+        let mut vec = tracked_vec(&drops, len, &model);
+        let first = MAX_CAP as u8;
+        let count = kani::any_where(|count| *count <= MAX_REPLACE);
+        let replace_with = Replacement {
+            drops: &drops,
+            len,
+            drained: start..end,
+            first,
+            next: first,
+            end: first + count,
+            lower_bound: kani::any_where(|bound| *bound <= usize::from(MAX_REPLACE) + 1),
+        };
+        {
+            let mut splice = vec.splice(start..end, replace_with);
+            for _ in 0..kani::any_where(|taken: &usize| *taken <= MAX_CAP) {
+                drop(splice.next());
+            }
+        }
+        let mut kept = [0; MAX_CAP + MAX_REPLACE as usize];
+        let kept_len = len - (end - start) + usize::from(count);
+        let ids = (0..start as u8).chain(first..first + count).chain(end as u8..len as u8);
+        for (i, id) in ids.enumerate() {
+            kept[i] = id;
+        }
+        assert!(vec.is_safe());
+        assert_eq!(vec.len(), kept_len);
+        unsafe { assert_ids(vec.as_ptr(), &kept[..kept_len]) };
+        assert_drops(&drops, 0..len as u8, &kept[..kept_len]);
+        assert_drops(&drops, first..first + count, &kept[..kept_len]);
+        drop(vec);
+        assert_drops(&drops, 0..len as u8, &[]);
+        assert_drops(&drops, first..first + count, &[]);
+        model.assert_no_leaks();
+    }
+
+    /// The replacement iterator panics in an arbitrary call to `next`.
+    #[kani::proof]
+    #[kani::unwind(8)]
+    #[kani::should_panic]
+    fn check_splice_panicking() {
+        let model = ModelAlloc::new();
+        let drops = [const { Cell::new(0) }; MAX_CAP + MAX_REPLACE as usize];
+        let len = kani::any_where(|len| *len <= MAX_CAP);
+        let end = kani::any_where(|end| *end <= len);
+        let start = kani::any_where(|start| *start <= end);
+        let mut vec = tracked_vec(&drops, len, &model);
+        let first = MAX_CAP as u8;
+        let count = kani::any_where(|count| *count <= MAX_REPLACE);
+        let panics_at = kani::any_where(|id| *id >= first && *id < first + count);
+        let replace_with = (first..first + count).map(|id| {
+            if id == panics_at {
+                panic!("the replacement iterator panicked");
+            }
+            Tracked::new(id, &drops)
+        });
+        drop(vec.splice(start..end, replace_with));
+    }
+}
@@ -1,7 +1,13 @@
+use safety::{ensures, requires};
+
 use crate::iter::{TrustedLen, UncheckedIterator};
 use crate::mem::ManuallyDrop;
 use crate::ptr::drop_in_place;
 use crate::slice;
+use crate::ub_checks::Invariant;
+
+#[cfg(kani)]
+use crate::kani;
 
 /// A situationally-optimized version of `array.into_iter().for_each(func)`.
 ///
@@ -31,7 +37,17 @@ pub(crate) fn drain_array_with<T, R, const N: usize>(
 // INVARIANT: It's ok to drop the remainder of the inner iterator.
 pub(crate) struct Drain<'a, T>(slice::IterMut<'a, T>);
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T> Invariant for Drain<'_, T> {
+    /// The remaining elements must be valid to read and write, so that they can be moved out
+    /// or dropped in place.
+    fn is_safe(&self) -> bool {
+        self.0.is_safe()
+    }
+}
+
 impl<T> Drop for Drain<'_, T> {
+    #[requires(self.is_safe())]
     fn drop(&mut self) {
         // SAFETY: By the type invariant, we're allowed to drop all these.
         unsafe { drop_in_place(self.0.as_mut_slice()) }
@@ -42,6 +58,10 @@ impl<T> Iterator for Drain<'_, T> {
     type Item = T;
 
     #[inline]
+    #[requires(self.is_safe())]
+    #[ensures(|item| item.is_some() == (old(self.len()) > 0))]
+    #[ensures(|_| self.is_safe() && self.len() == old(self.len()).saturating_sub(1))]
+    #[cfg_attr(kani, kani::modifies(self))]
     fn next(&mut self) -> Option<T> {
         let p: *const T = self.0.next()?;
         // SAFETY: The iterator was already advanced, so we won't drop this later.
@@ -66,6 +86,9 @@ impl<T> ExactSizeIterator for Drain<'_, T> {
 unsafe impl<T> TrustedLen for Drain<'_, T> {}
 
 impl<T> UncheckedIterator for Drain<'_, T> {
+    #[requires(self.len() > 0 && self.is_safe())]
+    #[ensures(|_| self.is_safe() && self.len() == old(self.len()) - 1)]
+    #[cfg_attr(kani, kani::modifies(self))]
     unsafe fn next_unchecked(&mut self) -> T {
         // SAFETY: `Drain` is 1:1 with the inner iterator, so if the caller promised
         // that there's an element left, the inner iterator has one too.
@@ -74,3 +97,47 @@ impl<T> UncheckedIterator for Drain<'_, T> {
         unsafe { p.read() }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::array::Tracked;
+    use crate::cell::Cell;
+
+    const N: usize = 4;
+
+    #[kani::proof_for_contract(<Drain<'_, u8> as Iterator>::next)]
+    fn check_next() {
+        let array: [u8; N] = kani::any();
+        drain_array_with(array, |mut drain| {
+            let _ = drain.next();
+        });
+    }
+
+    #[kani::proof_for_contract(<Drain<'_, u8> as UncheckedIterator>::next_unchecked)]
+    fn check_next_unchecked() {
+        let array: [u8; N] = kani::any();
+        drain_array_with(array, |mut drain| {
+            let _ = unsafe { drain.next_unchecked() };
+        });
+    }
+
+    /// Takes an arbitrary number of elements out of the drain and checks that, once `func`
+    /// returns, every element was dropped exactly once.
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_elements_dropped_once() {
+        let drops = [const { Cell::new(0) }; N];
+        let array = crate::array::from_fn::<_, N, _>(|i| Tracked::new(i as u8, &drops));
+        let taken = kani::any_where(|n: &usize| *n <= N);
+        drain_array_with(array, |mut drain| {
+            for _ in 0..taken {
+                drop(drain.next());
+            }
+            assert!(drain.is_safe());
+            assert!(drain.len() == N - taken);
+        });
+        assert!(drops.iter().all(|d| d.get() == 1));
+    }
+}
@@ -1,12 +1,18 @@
 //! Defines the `IntoIter` owned iterator for arrays.
 
+use safety::{ensures, requires};
+
 use crate::intrinsics::transmute_unchecked;
 use crate::iter::{self, FusedIterator, TrustedLen, TrustedRandomAccessNoCoerce};
 use crate::mem::MaybeUninit;
 use crate::num::NonZero;
 use crate::ops::{IndexRange, Range};
+use crate::ub_checks::Invariant;
 use crate::{fmt, ptr};
 
+#[cfg(kani)]
+use crate::kani;
+
 /// A by-value [array] iterator.
 #[stable(feature = "array_value_iter", since = "1.51.0")]
 #[rustc_insignificant_dtor]
@@ -137,6 +143,7 @@ impl<T, const N: usize> IntoIter<T, N> {
     /// ```
     #[unstable(feature = "array_into_iter_constructors", issue = "91583")]
     #[rustc_const_unstable(feature = "const_array_into_iter_constructors", issue = "91583")]
+    #[requires(initialized.start <= initialized.end && initialized.end <= N)]
     pub const unsafe fn new_unchecked(
         buffer: [MaybeUninit<T>; N],
         initialized: Range<usize>,
@@ -212,6 +219,8 @@ impl<T, const N: usize> IntoIter<T, N> {
     /// Returns an immutable slice of all elements that have not been yielded
     /// yet.
     #[stable(feature = "array_value_iter", since = "1.51.0")]
+    #[requires(self.is_safe())]
+    #[ensures(|slice: &&[T]| slice.len() == self.alive.len())]
     pub fn as_slice(&self) -> &[T] {
         // SAFETY: We know that all elements within `alive` are properly initialized.
         unsafe {
@@ -234,6 +243,10 @@ impl<T, const N: usize> IntoIter<T, N> {
 #[stable(feature = "array_value_iter_impls", since = "1.40.0")]
 impl<T, const N: usize> Iterator for IntoIter<T, N> {
     type Item = T;
+    #[requires(self.is_safe())]
+    #[ensures(|item| item.is_some() == (old(self.len()) > 0))]
+    #[ensures(|_| self.is_safe() && self.len() == old(self.len()).saturating_sub(1))]
+    #[cfg_attr(kani, kani::modifies(self))]
     fn next(&mut self) -> Option<Self::Item> {
         // Get the next index from the front.
         //
@@ -278,6 +291,10 @@ impl<T, const N: usize> Iterator for IntoIter<T, N> {
         self.next_back()
     }
 
+    #[requires(self.is_safe())]
+    #[ensures(|result| result.is_ok() == (n <= old(self.len())))]
+    #[ensures(|_| self.is_safe() && self.len() == old(self.len()).saturating_sub(n))]
+    #[cfg_attr(kani, kani::modifies(self))]
     fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
         // This also moves the start, which marks them as conceptually "dropped",
         // so if anything goes bad then our drop impl won't double-free them.
@@ -302,6 +319,10 @@ impl<T, const N: usize> Iterator for IntoIter<T, N> {
 
 #[stable(feature = "array_value_iter_impls", since = "1.40.0")]
 impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    #[requires(self.is_safe())]
+    #[ensures(|item| item.is_some() == (old(self.len()) > 0))]
+    #[ensures(|_| self.is_safe() && self.len() == old(self.len()).saturating_sub(1))]
+    #[cfg_attr(kani, kani::modifies(self))]
     fn next_back(&mut self) -> Option<Self::Item> {
         // Get the next index from the back.
         //
@@ -351,6 +372,7 @@ impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
 
 #[stable(feature = "array_value_iter_impls", since = "1.40.0")]
 impl<T, const N: usize> Drop for IntoIter<T, N> {
+    #[requires(self.is_safe())]
     fn drop(&mut self) {
         // SAFETY: This is safe: `as_mut_slice` returns exactly the sub-slice
         // of elements that have not been moved out yet and that remain
@@ -359,6 +381,16 @@ impl<T, const N: usize> Drop for IntoIter<T, N> {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T, const N: usize> Invariant for IntoIter<T, N> {
+    /// The `alive` range has to stay within `data`. The other half of the invariant, that
+    /// exactly `data[alive]` is initialized, cannot be checked from here; the harnesses below
+    /// cover it by counting drops.
+    fn is_safe(&self) -> bool {
+        self.alive.start() <= self.alive.end() && self.alive.end() <= N
+    }
+}
+
 #[stable(feature = "array_value_iter_impls", since = "1.40.0")]
 impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {
     fn len(&self) -> usize {
@@ -427,3 +459,81 @@ impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
         f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::array::Tracked;
+    use crate::cell::Cell;
+
+    const N: usize = 4;
+
+    /// An iterator whose alive range is an arbitrary sub-range of a fully initialized array.
+    fn any_into_iter() -> IntoIter<u8, N> {
+        let data: [u8; N] = kani::any();
+        let end = kani::any_where(|end: &usize| *end <= N);
+        let start = kani::any_where(|start: &usize| *start <= end);
+        unsafe { IntoIter::new_unchecked(data.map(MaybeUninit::new), start..end) }
+    }
+
+    #[kani::proof_for_contract(IntoIter::<u8, N>::new_unchecked)]
+    fn check_new_unchecked() {
+        let data: [u8; N] = kani::any();
+        let range: Range<usize> = kani::any::<usize>()..kani::any::<usize>();
+        let it = unsafe { IntoIter::new_unchecked(data.map(MaybeUninit::new), range.clone()) };
+        assert!(it.is_safe());
+        assert!(it.as_slice() == &data[range]);
+    }
+
+    #[kani::proof_for_contract(IntoIter::<u8, N>::as_slice)]
+    fn check_as_slice() {
+        let it = any_into_iter();
+        let _ = it.as_slice();
+    }
+
+    #[kani::proof_for_contract(<IntoIter<u8, N> as Iterator>::next)]
+    fn check_next() {
+        let mut it = any_into_iter();
+        let _ = it.next();
+    }
+
+    #[kani::proof_for_contract(<IntoIter<u8, N> as DoubleEndedIterator>::next_back)]
+    fn check_next_back() {
+        let mut it = any_into_iter();
+        let _ = it.next_back();
+    }
+
+    #[kani::proof_for_contract(<IntoIter<u8, N> as Iterator>::advance_by)]
+    fn check_advance_by() {
+        let mut it = any_into_iter();
+        let _ = it.advance_by(kani::any());
+    }
+
+    #[kani::proof_for_contract(<IntoIter<u8, N> as Drop>::drop)]
+    fn check_drop() {
+        let it = any_into_iter();
+        drop(it);
+    }
+
+    /// Consumes the iterator from both ends in an arbitrary order and checks that, once it is
+    /// dropped, every element was dropped exactly once.
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_elements_dropped_once() {
+        let drops = [const { Cell::new(0) }; N];
+        let array = crate::array::from_fn::<_, N, _>(|i| Tracked::new(i as u8, &drops));
+        let mut it = array.into_iter();
+        for _ in 0..N {
+            match kani::any::<u8>() {
+                0 => drop(it.next()),
+                1 => drop(it.next_back()),
+                2 => drop(it.advance_by(kani::any_where(|n: &usize| *n <= N))),
+                _ => drop(it.advance_back_by(kani::any_where(|n: &usize| *n <= N))),
+            }
+            assert!(it.is_safe());
+        }
+        drop(it);
+        assert!(drops.iter().all(|d| d.get() == 1));
+    }
+}
@@ -4,6 +4,8 @@
 
 #![stable(feature = "core_array", since = "1.35.0")]
 
+use safety::{ensures, requires};
+
 use crate::borrow::{Borrow, BorrowMut};
 use crate::cmp::Ordering;
 use crate::convert::Infallible;
@@ -16,6 +18,10 @@ use crate::ops::{
     ChangeOutputType, ControlFlow, FromResidual, Index, IndexMut, NeverShortCircuit, Residual, Try,
 };
 use crate::slice::{Iter, IterMut};
+use crate::ub_checks::Invariant;
+
+#[cfg(kani)]
+use crate::kani;
 
 mod ascii;
 mod drain;
@@ -878,6 +884,9 @@ impl<T> Guard<'_, T> {
     ///
     /// No more than N elements must be initialized.
     #[inline]
+    #[requires(self.is_safe() && self.initialized < self.array_mut.len())]
+    #[ensures(|_| self.is_safe() && self.initialized == old(self.initialized) + 1)]
+    #[cfg_attr(kani, kani::modifies(self, self.array_mut))]
     pub unsafe fn push_unchecked(&mut self, item: T) {
         // SAFETY: If `initialized` was correct before and the caller does not
         // invoke this method more than N times then writes will be in-bounds
@@ -889,8 +898,18 @@ impl<T> Guard<'_, T> {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<T> Invariant for Guard<'_, T> {
+    /// `initialized` counts the prefix of `array_mut` that holds valid elements, so it can't
+    /// exceed the buffer.
+    fn is_safe(&self) -> bool {
+        self.initialized <= self.array_mut.len()
+    }
+}
+
 impl<T> Drop for Guard<'_, T> {
     #[inline]
+    #[requires(self.is_safe())]
     fn drop(&mut self) {
         debug_assert!(self.initialized <= self.array_mut.len());
 
@@ -960,3 +979,134 @@ fn iter_next_chunk_erased<T>(
     mem::forget(guard);
     Ok(())
 }
+
+/// An array element that counts how many times it has been dropped.
+///
+/// Every element carries an id, which indexes into a table of drop counters owned by the
+/// harness. This is the `Tracked` of alloc's `alloc::model`, which core cannot depend on; the
+/// harnesses of this module and its submodules fill arrays with it to check that guards and
+/// iterators drop every element exactly once.
+#[cfg(kani)]
+#[derive(Debug)]
+struct Tracked<'a> {
+    id: u8,
+    drops: &'a [crate::cell::Cell<u8>],
+}
+
+#[cfg(kani)]
+impl<'a> Tracked<'a> {
+    /// Creates an element whose drops are counted in `drops[id]`.
+    fn new(id: u8, drops: &'a [crate::cell::Cell<u8>]) -> Self {
+        Tracked { id, drops }
+    }
+
+    /// Returns the id this element was created with.
+    fn id(&self) -> u8 {
+        self.id
+    }
+}
+
+#[cfg(kani)]
+impl Drop for Tracked<'_> {
+    fn drop(&mut self) {
+        let drops = &self.drops[usize::from(self.id)];
+        drops.set(drops.get() + 1);
+    }
+}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::cell::Cell;
+
+    const N: usize = 4;
+
+    // Kani does not model unwinding. A generator that panics leaves `try_from_fn_erased`
+    // through the same guard drop as one that returns a residual, so the harnesses below use
+    // `None` to stand in for both.
+
+    #[kani::proof_for_contract(Guard::<u8>::push_unchecked)]
+    fn check_push_unchecked() {
+        let mut buffer = [MaybeUninit::<u8>::uninit(); N];
+        let initialized = kani::any_where(|n: &usize| *n <= N);
+        for slot in &mut buffer[..initialized] {
+            slot.write(kani::any());
+        }
+        let mut guard = Guard { array_mut: &mut buffer, initialized };
+        let item: u8 = kani::any();
+        unsafe { guard.push_unchecked(item) };
+        assert!(unsafe { guard.array_mut[initialized].assume_init() } == item);
+        mem::forget(guard);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_from_fn() {
+        let array: [usize; N] = from_fn(|i| i);
+        assert!((0..N).all(|i| array[i] == i));
+    }
+
+    /// Fails at an arbitrary index, or not at all, and checks that a failure drops exactly
+    /// the elements produced so far, while success hands all of them over in order.
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_try_from_fn_drops_initialized() {
+        let drops = [const { Cell::new(0) }; N];
+        let fail_at = kani::any_where(|i: &usize| *i <= N);
+        let result =
+            try_from_fn::<_, N, _>(|i| (i != fail_at).then(|| Tracked::new(i as u8, &drops)));
+        match result {
+            None => {
+                assert!(fail_at < N);
+                assert!((0..N).all(|i| drops[i].get() == (i < fail_at) as u8));
+            }
+            Some(array) => {
+                assert!(fail_at == N);
+                assert!(drops.iter().all(|d| d.get() == 0));
+                assert!((0..N).all(|i| array[i].id() == i as u8));
+                drop(array);
+                assert!(drops.iter().all(|d| d.get() == 1));
+            }
+        }
+    }
+
+    /// Feeds an arbitrary mix of `Some` and `None` through `try_from_trusted_iterator` and
+    /// checks that every element is dropped exactly once, whether it ended up in the array,
+    /// in the guard, or was never pulled from the source.
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_try_from_trusted_iterator_drops_once() {
+        let drops = [const { Cell::new(0) }; N];
+        let present: [bool; N] = kani::any();
+        let items: [Option<Tracked<'_>>; N] =
+            from_fn(|i| present[i].then(|| Tracked::new(i as u8, &drops)));
+        let result =
+            drain_array_with(items, |drain| try_from_trusted_iterator::<Tracked<'_>, _, N>(drain));
+        assert!(result.is_some() == present.iter().all(|p| *p));
+        drop(result);
+        assert!((0..N).all(|i| drops[i].get() == present[i] as u8));
+    }
+
+    /// Pulls a chunk out of an iterator that may run short, and checks that every element it
+    /// yielded is dropped exactly once and nothing past the chunk is pulled.
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_iter_next_chunk() {
+        let drops = [const { Cell::new(0) }; N + 1];
+        let len = kani::any_where(|len: &usize| *len <= N + 1);
+        let mut iter = (0..len).map(|i| Tracked::new(i as u8, &drops));
+        match iter_next_chunk::<_, N>(&mut iter) {
+            Ok(array) => {
+                assert!(len >= N);
+                assert!((0..N).all(|i| array[i].id() == i as u8));
+            }
+            Err(rest) => {
+                assert!(len < N);
+                assert!(rest.len() == len);
+            }
+        }
+        assert!((0..len.min(N)).all(|i| drops[i].get() == 1));
+        assert!(drops[N].get() == 0);
+    }
+}
@@ -7,6 +7,8 @@ use crate::fmt;
 use crate::mem::transmute;
 use crate::str::FromStr;
 use crate::ub_checks::assert_unsafe_precondition;
+#[allow(unused_imports)]
+use crate::ub_checks::Invariant;
 
 #[cfg(kani)]
 use crate::kani;
@@ -26,7 +28,7 @@ pub(super) const fn from_u32(i: u32) -> Option<char> {
 #[inline]
 #[must_use]
 #[requires(char_try_from_u32(i).is_ok())]
-#[ensures(|result| *result as u32 == i)]
+#[ensures(|result| *result as u32 == i && result.is_safe())]
 pub(super) const unsafe fn from_u32_unchecked(i: u32) -> char {
     // SAFETY: the caller must guarantee that `i` is a valid char value.
     unsafe {
@@ -285,6 +287,7 @@ impl fmt::Display for CharTryFromError {
 /// Converts a digit in the given radix to a `char`. See [`char::from_digit`].
 #[inline]
 #[must_use]
+#[ensures(|result| result.map_or(num >= radix, |c| c.is_ascii_alphanumeric() && c.is_safe()))]
 pub(super) const fn from_digit(num: u32, radix: u32) -> Option<char> {
     if radix > 36 {
         panic!("from_digit: radix is too high (maximum 36)");
@@ -307,4 +310,42 @@ mod verify {
         let i: u32 = kani::any();
         unsafe { from_u32_unchecked(i) };
     }
+
+    #[kani::proof]
+    fn check_from_u32() {
+        let i: u32 = kani::any();
+        if let Some(c) = from_u32(i) {
+            assert!(c.is_safe());
+            assert_eq!(c as u32, i);
+        } else {
+            assert!((0xD800..=0xDFFF).contains(&i) || i > 0x10FFFF);
+        }
+    }
+
+    #[kani::proof]
+    fn check_try_from_u32() {
+        let i: u32 = kani::any();
+        if let Ok(c) = char::try_from(i) {
+            assert!(c.is_safe());
+        }
+    }
+
+    #[kani::proof_for_contract(from_digit)]
+    fn check_from_digit() {
+        let radix = kani::any_where(|radix: &u32| *radix <= 36);
+        let _ = from_digit(kani::any(), radix);
+    }
+
+    #[kani::proof]
+    fn check_to_digit() {
+        let c: char = kani::any();
+        let radix = kani::any_where(|radix: &u32| *radix >= 2 && *radix <= 36);
+        if let Some(digit) = c.to_digit(radix) {
+            assert!(digit < radix);
+            // Converting the digit back yields a valid `char` that matches `c` up to case.
+            let back = from_digit(digit, radix).unwrap();
+            assert!(back.is_safe());
+            assert_eq!(back, c.to_ascii_lowercase());
+        }
+    }
 }
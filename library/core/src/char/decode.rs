@@ -132,3 +132,40 @@ impl Error for DecodeUtf16Error {
         "unpaired surrogate found"
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::ub_checks::Invariant;
+
+    const MAX_LEN: usize = 4;
+
+    /// Every unit ends up in exactly one item: a valid char, which is a surrogate pair exactly
+    /// when it starts with a surrogate, or the error for an unpaired surrogate. In particular, a
+    /// unit rejected as the second half of a pair is decoded again on its own, not dropped.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_decode_utf16_yields_safe_chars() {
+        let units: [u16; MAX_LEN] = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let mut consumed = 0;
+        for decoded in decode_utf16(units[..len].iter().copied()) {
+            let first = units[consumed];
+            match decoded {
+                Ok(c) => {
+                    assert!(c.is_safe());
+                    assert_eq!(c.len_utf16() == 2, first.is_utf16_surrogate());
+                    consumed += c.len_utf16();
+                }
+                Err(e) => {
+                    assert!(first.is_utf16_surrogate());
+                    assert_eq!(e.unpaired_surrogate(), first);
+                    consumed += 1;
+                }
+            }
+        }
+        assert_eq!(consumed, len);
+    }
+}
@@ -1,5 +1,7 @@
 //! impl char {}
 
+use safety::{ensures, requires};
+
 use super::*;
 use crate::intrinsics::const_eval_select;
 use crate::slice;
@@ -1778,6 +1780,8 @@ const fn len_utf16(code: u32) -> usize {
 #[doc(hidden)]
 #[inline]
 #[rustc_allow_const_fn_unstable(const_eval_select)]
+#[requires(len_utf8(code) <= dst.len())]
+#[ensures(|result| result.len() == len_utf8(code))]
 pub const fn encode_utf8_raw(code: u32, dst: &mut [u8]) -> &mut [u8] {
     const fn panic_at_const(_code: u32, _len: usize, _dst_len: usize) {
         // Note that we cannot format in constant expressions.
@@ -1829,6 +1833,8 @@ pub const fn encode_utf8_raw(code: u32, dst: &mut [u8]) -> &mut [u8] {
 #[rustc_const_unstable(feature = "const_char_encode_utf16", issue = "130660")]
 #[doc(hidden)]
 #[inline]
+#[requires(len_utf16(code) <= dst.len())]
+#[ensures(|result| result.len() == len_utf16(code))]
 pub const fn encode_utf16_raw(mut code: u32, dst: &mut [u16]) -> &mut [u16] {
     const fn panic_at_const(_code: u32, _len: usize, _dst_len: usize) {
         // Note that we cannot format in constant expressions.
@@ -1860,7 +1866,7 @@ pub const fn encode_utf16_raw(mut code: u32, dst: &mut [u16]) -> &mut [u16] {
 #[unstable(feature="kani", issue="none")]
 mod verify {
     use super::*;
-    use safety::ensures;
+    use crate::ub_checks::Invariant;
 
     #[ensures(|result| c.is_ascii() == (result.is_some() && (result.unwrap() as u8 as char == *c)))]
     fn as_ascii_clone(c: &char) -> Option<ascii::Char> {
@@ -1878,4 +1884,44 @@ mod verify {
         let non_ascii: char = kani::any_where(|c: &char| !c.is_ascii());
         as_ascii_clone(&non_ascii);
     }
+
+    #[kani::proof_for_contract(encode_utf8_raw)]
+    fn check_encode_utf8_raw() {
+        let mut buf = [0u8; 4];
+        let len = kani::any_where(|len: &usize| *len <= buf.len());
+        encode_utf8_raw(kani::any(), &mut buf[..len]);
+    }
+
+    #[kani::proof_for_contract(encode_utf16_raw)]
+    fn check_encode_utf16_raw() {
+        let mut buf = [0u16; 2];
+        let len = kani::any_where(|len: &usize| *len <= buf.len());
+        encode_utf16_raw(kani::any(), &mut buf[..len]);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_utf8_round_trip() {
+        let c: char = kani::any();
+        let mut buf = [0u8; 4];
+        let encoded = c.encode_utf8(&mut buf);
+        assert_eq!(encoded.len(), c.len_utf8());
+        let mut chars = encoded.chars();
+        let decoded = chars.next().unwrap();
+        assert!(decoded.is_safe());
+        assert_eq!(decoded, c);
+        assert!(chars.next().is_none());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(3)]
+    fn check_utf16_round_trip() {
+        let c: char = kani::any();
+        let mut buf = [0u16; 2];
+        let encoded = c.encode_utf16(&mut buf);
+        assert_eq!(encoded.len(), c.len_utf16());
+        let mut decoded = char::decode_utf16(encoded.iter().copied());
+        assert_eq!(decoded.next(), Some(Ok(c)));
+        assert!(decoded.next().is_none());
+    }
 }
@@ -1,10 +1,15 @@
 //! Integer and floating-point number formatting
 
+use safety::{ensures, requires};
+
 use crate::mem::MaybeUninit;
 use crate::num::fmt as numfmt;
 use crate::ops::{Div, Rem, Sub};
 use crate::{fmt, ptr, slice, str};
 
+#[cfg(kani)]
+use crate::kani;
+
 #[doc(hidden)]
 trait DisplayInt:
     PartialEq + PartialOrd + Div<Output = Self> + Rem<Output = Self> + Sub<Output = Self> + Copy
@@ -562,6 +567,15 @@ mod imp {
 impl_Exp!(i128, u128 as u128 via to_u128 named exp_u128);
 
 /// Helper function for writing a u64 into `buf` going from last to first, with `curr`.
+// Callers only pass remainders of `udiv_1e19`, which have at most 19 digits.
+#[requires(n < 10_000_000_000_000_000_000)]
+#[requires(*curr > 19 && *curr <= N)]
+#[cfg_attr(kani, kani::modifies(buf, curr))]
+#[ensures(|_| *curr < old(*curr) && old(*curr) - *curr <= 19)]
+#[ensures(|_| buf[*curr..old(*curr)].iter().all(|b| {
+    // SAFETY: every byte in `buf[*curr..old(*curr)]` has just been written by this function.
+    unsafe { b.assume_init() }.is_ascii_digit()
+}))]
 fn parse_u64_into<const N: usize>(mut n: u64, buf: &mut [MaybeUninit<u8>; N], curr: &mut usize) {
     let buf_ptr = MaybeUninit::slice_as_mut_ptr(buf);
     let lut_ptr = DEC_DIGITS_LUT.as_ptr();
@@ -732,6 +746,7 @@ fn fmt_u128(n: u128, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::R
 ///   in Proc. of the SIGPLAN94 Conference on Programming Language Design and
 ///   Implementation, 1994, pp. 61–72
 ///
+#[ensures(|(quot, rem)| *rem < 1e19 as u64 && *quot * 1e19 as u128 + *rem as u128 == n)]
 fn udiv_1e19(n: u128) -> (u128, u64) {
     const DIV: u64 = 1e19 as u64;
     const FACTOR: u128 = 156927543384667019095894735580191660403;
@@ -764,3 +779,105 @@ fn u128_mulhi(x: u128, y: u128) -> u128 {
 
     x_hi as u128 * y_hi as u128 + high1 + high2
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+
+    /// A fixed-capacity `fmt::Write` sink, since `core` has no `String` to format into.
+    ///
+    /// The capacity fits any integer formatted with a precision of up to `MAX_PRECISION`.
+    struct Sink {
+        buf: [u8; 128],
+        len: usize,
+    }
+
+    const MAX_PRECISION: usize = 64;
+
+    impl Sink {
+        fn new() -> Self {
+            Sink { buf: [0; 128], len: 0 }
+        }
+
+        /// Checks that everything written so far is valid UTF-8.
+        fn as_str(&self) -> &str {
+            str::from_utf8(&self.buf[..self.len]).unwrap()
+        }
+    }
+
+    impl fmt::Write for Sink {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len.checked_add(s.len()).ok_or(fmt::Error)?;
+            self.buf.get_mut(self.len..end).ok_or(fmt::Error)?.copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[kani::proof_for_contract(parse_u64_into)]
+    fn check_parse_u64_into() {
+        let mut buf = [MaybeUninit::<u8>::uninit(); 39];
+        let mut curr = kani::any_where(|curr: &usize| *curr > 19 && *curr <= 39);
+        let n = kani::any_where(|n: &u64| *n < 10_000_000_000_000_000_000);
+        parse_u64_into(n, &mut buf, &mut curr);
+    }
+
+    #[kani::proof_for_contract(udiv_1e19)]
+    fn check_udiv_1e19() {
+        let _ = udiv_1e19(kani::any());
+    }
+
+    macro_rules! check_int_fmt {
+        ($($module:ident, $t:ty;)*) => {$(
+            mod $module {
+                use super::*;
+
+                /// `Display` emits only ASCII digits after an optional sign, and the emitted
+                /// range parses back to the same value.
+                #[kani::proof]
+                #[kani::unwind(41)]
+                fn check_display() {
+                    let x: $t = kani::any();
+                    let mut sink = Sink::new();
+                    fmt::write(&mut sink, format_args!("{}", x)).unwrap();
+                    let s = sink.as_str();
+                    let digits = s.strip_prefix('-').unwrap_or(s);
+                    assert!(!digits.is_empty());
+                    assert!(digits.bytes().all(|b| b.is_ascii_digit()));
+                    assert_eq!(s.parse::<$t>(), Ok(x));
+                }
+
+                #[kani::proof]
+                #[kani::unwind(65)]
+                fn check_exp() {
+                    let x: $t = kani::any();
+                    let precision = kani::any_where(|p: &usize| *p <= MAX_PRECISION);
+                    let mut sink = Sink::new();
+                    if kani::any() {
+                        fmt::write(&mut sink, format_args!("{:.*e}", precision, x)).unwrap();
+                    } else {
+                        fmt::write(&mut sink, format_args!("{:E}", x)).unwrap();
+                    }
+                    let s = sink.as_str();
+                    assert!(s.bytes().all(|b| b.is_ascii_digit() || b"-.eE".contains(&b)));
+                }
+            }
+        )*};
+    }
+
+    check_int_fmt! {
+        verify_i8, i8;
+        verify_u8, u8;
+        verify_i16, i16;
+        verify_u16, u16;
+        verify_i32, i32;
+        verify_u32, u32;
+        verify_i64, i64;
+        verify_u64, u64;
+        verify_i128, i128;
+        verify_u128, u128;
+        verify_isize, isize;
+        verify_usize, usize;
+    }
+}
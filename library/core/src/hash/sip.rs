@@ -2,7 +2,12 @@
 
 #![allow(deprecated)] // the types in this module are deprecated
 
+use safety::{ensures, requires};
+
+#[cfg(kani)]
+use crate::kani;
 use crate::marker::PhantomData;
+use crate::ub_checks::Invariant;
 use crate::{cmp, mem, ptr};
 
 /// An implementation of SipHash 1-3.
@@ -117,6 +122,9 @@ macro_rules! load_int_le {
 /// Safety: this performs unchecked indexing of `buf` at `start..start+len`, so
 /// that must be in-bounds.
 #[inline]
+#[requires(len < 8 && start.checked_add(len).is_some_and(|end| end <= buf.len()))]
+#[ensures(|out| *out >> (8 * len) == 0)]
+#[ensures(|out| (0..len).all(|j| (*out >> (8 * j)) as u8 == buf[start + j]))]
 unsafe fn u8to64_le(buf: &[u8], start: usize, len: usize) -> u64 {
     debug_assert!(len < 8);
     let mut i = 0; // current byte index (from LSB) in the output u64
@@ -256,6 +264,9 @@ impl<S: Sip> super::Hasher for Hasher<S> {
     // slightly slowing down compile speeds on some benchmarks. See #69152 for
     // details.
     #[inline]
+    #[requires(self.is_safe() && self.length.checked_add(msg.len()).is_some())]
+    #[ensures(|_| self.is_safe() && self.length == old(self.length) + msg.len())]
+    #[cfg_attr(kani, kani::modifies(self))]
     fn write(&mut self, msg: &[u8]) {
         let length = msg.len();
         self.length += length;
@@ -327,6 +338,16 @@ impl<S: Sip> super::Hasher for Hasher<S> {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<S: Sip> Invariant for Hasher<S> {
+    /// The tail buffers fewer than 8 bytes, and every bit above the buffered
+    /// bytes is zero so that `write` can OR new bytes in and `finish` can
+    /// pack the length into the top byte.
+    fn is_safe(&self) -> bool {
+        self.ntail < 8 && self.tail >> (8 * self.ntail) == 0
+    }
+}
+
 impl<S: Sip> Clone for Hasher<S> {
     #[inline]
     fn clone(&self) -> Hasher<S> {
@@ -391,3 +412,83 @@ impl Sip for Sip24Rounds {
         compress!(state);
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::hash::Hasher as _;
+
+    /// Longest message the harnesses hash; covers an empty tail, a flushed
+    /// tail and at least one full 8-byte block after a partial one.
+    const MAX_LEN: usize = 20;
+
+    fn any_msg(bytes: &[u8; MAX_LEN]) -> &[u8] {
+        let len: usize = kani::any_where(|len| *len <= MAX_LEN);
+        &bytes[..len]
+    }
+
+    /// A hasher with arbitrary keys that has already buffered an arbitrary
+    /// number of tail bytes.
+    fn any_hasher<S: Sip>() -> Hasher<S> {
+        let mut hasher = Hasher::<S>::new_with_keys(kani::any(), kani::any());
+        let prefix: [u8; 7] = kani::any();
+        let len: usize = kani::any_where(|len| *len < 8);
+        hasher.write(&prefix[..len]);
+        hasher
+    }
+
+    #[kani::proof_for_contract(u8to64_le)]
+    #[kani::unwind(8)]
+    fn check_u8to64_le() {
+        let buf: [u8; 16] = kani::any();
+        let slice = &buf[..kani::any_where(|len: &usize| *len <= 16)];
+        let start: usize = kani::any();
+        let len: usize = kani::any();
+        unsafe {
+            u8to64_le(slice, start, len);
+        }
+    }
+
+    #[kani::proof_for_contract(<Hasher<Sip13Rounds> as crate::hash::Hasher>::write)]
+    #[kani::unwind(4)]
+    fn check_write() {
+        let bytes: [u8; MAX_LEN] = kani::any();
+        let mut hasher = any_hasher::<Sip13Rounds>();
+        hasher.write(any_msg(&bytes));
+    }
+
+    /// Hashing a message in one `write` gives the same state and digest as
+    /// feeding it in three arbitrary chunks.
+    fn check_chunking_equivalence<S: Sip>() {
+        let bytes: [u8; MAX_LEN] = kani::any();
+        let msg = any_msg(&bytes);
+        let first: usize = kani::any_where(|i| *i <= msg.len());
+        let second: usize = kani::any_where(|i| first <= *i && *i <= msg.len());
+
+        let mut whole = Hasher::<S>::new_with_keys(kani::any(), kani::any());
+        let mut chunked = whole.clone();
+        whole.write(msg);
+        chunked.write(&msg[..first]);
+        chunked.write(&msg[first..second]);
+        chunked.write(&msg[second..]);
+
+        assert!(whole.is_safe() && chunked.is_safe());
+        assert_eq!(whole.length, chunked.length);
+        assert_eq!(whole.ntail, chunked.ntail);
+        assert_eq!(whole.tail, chunked.tail);
+        assert_eq!(whole.finish(), chunked.finish());
+    }
+
+    #[kani::proof]
+    #[kani::unwind(4)]
+    fn check_chunking_equivalence_sip13() {
+        check_chunking_equivalence::<Sip13Rounds>();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(4)]
+    fn check_chunking_equivalence_sip24() {
+        check_chunking_equivalence::<Sip24Rounds>();
+    }
+}
@@ -1,9 +1,15 @@
 #![unstable(feature = "core_io_borrowed_buf", issue = "117693")]
 
+use safety::{ensures, requires};
+
 use crate::fmt::{self, Debug, Formatter};
 use crate::mem::{self, MaybeUninit};
+use crate::ub_checks::Invariant;
 use crate::{cmp, ptr};
 
+#[cfg(kani)]
+use crate::kani;
+
 /// A borrowed byte buffer which is incrementally filled and initialized.
 ///
 /// This type is a sort of "double cursor". It tracks three regions in the buffer: a region at the beginning of the
@@ -34,6 +40,14 @@ pub struct BorrowedBuf<'data> {
     init: usize,
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl Invariant for BorrowedBuf<'_> {
+    /// The filled region is part of the initialized region, which is part of the buffer.
+    fn is_safe(&self) -> bool {
+        self.filled <= self.init && self.init <= self.buf.len()
+    }
+}
+
 impl Debug for BorrowedBuf<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("BorrowedBuf")
@@ -139,6 +153,9 @@ impl<'data> BorrowedBuf<'data> {
     ///
     /// The caller must ensure that the first `n` unfilled bytes of the buffer have already been initialized.
     #[inline]
+    #[requires(self.is_safe() && n <= self.capacity())]
+    #[ensures(|buf: &&mut Self| buf.is_safe() && buf.init == cmp::max(old(self.init), n))]
+    #[cfg_attr(kani, kani::modifies(self))]
     pub unsafe fn set_init(&mut self, n: usize) -> &mut Self {
         self.init = cmp::max(self.init, n);
         self
@@ -173,6 +190,14 @@ pub struct BorrowedCursor<'a> {
     start: usize,
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl Invariant for BorrowedCursor<'_> {
+    /// The cursor can only see the part of a valid buffer that was unfilled when it was created.
+    fn is_safe(&self) -> bool {
+        self.buf.is_safe() && self.start <= self.buf.filled
+    }
+}
+
 impl<'a> BorrowedCursor<'a> {
     /// Reborrows this cursor by cloning it with a smaller lifetime.
     ///
@@ -242,6 +267,8 @@ impl<'a> BorrowedCursor<'a> {
     ///
     /// The caller must not uninitialize any bytes in the initialized portion of the cursor.
     #[inline]
+    #[requires(self.is_safe())]
+    #[ensures(|slice: &&mut [MaybeUninit<u8>]| slice.len() == old(self.capacity()))]
     pub unsafe fn as_mut(&mut self) -> &mut [MaybeUninit<u8>] {
         // SAFETY: always in bounds
         unsafe { self.buf.buf.get_unchecked_mut(self.buf.filled..) }
@@ -279,6 +306,9 @@ impl<'a> BorrowedCursor<'a> {
     /// The caller must ensure that the first `n` bytes of the cursor have been properly
     /// initialised.
     #[inline]
+    #[requires(self.is_safe() && n <= self.capacity())]
+    #[ensures(|cursor: &&mut Self| cursor.is_safe() && cursor.written() == old(self.written()) + n)]
+    #[cfg_attr(kani, kani::modifies(self.buf))]
     pub unsafe fn advance_unchecked(&mut self, n: usize) -> &mut Self {
         self.buf.filled += n;
         self.buf.init = cmp::max(self.buf.init, self.buf.filled);
@@ -287,6 +317,9 @@ impl<'a> BorrowedCursor<'a> {
 
     /// Initializes all bytes in the cursor.
     #[inline]
+    #[requires(self.is_safe())]
+    #[ensures(|cursor: &&mut Self| cursor.is_safe() && cursor.init_ref().len() == cursor.capacity())]
+    #[cfg_attr(kani, kani::modifies(self.buf, self.buf.buf))]
     pub fn ensure_init(&mut self) -> &mut Self {
         let uninit = self.uninit_mut();
         // SAFETY: 0 is a valid value for MaybeUninit<u8> and the length matches the allocation
@@ -308,6 +341,9 @@ impl<'a> BorrowedCursor<'a> {
     ///
     /// The caller must ensure that the first `n` bytes of the buffer have already been initialized.
     #[inline]
+    #[requires(self.is_safe() && n <= self.capacity())]
+    #[ensures(|cursor: &&mut Self| cursor.is_safe() && cursor.init_ref().len() >= n)]
+    #[cfg_attr(kani, kani::modifies(self.buf))]
     pub unsafe fn set_init(&mut self, n: usize) -> &mut Self {
         self.buf.init = cmp::max(self.buf.init, self.buf.filled + n);
         self
@@ -319,6 +355,9 @@ impl<'a> BorrowedCursor<'a> {
     ///
     /// Panics if `self.capacity()` is less than `buf.len()`.
     #[inline]
+    #[requires(self.is_safe())]
+    #[ensures(|_| self.is_safe() && self.written() == old(self.written()) + buf.len())]
+    #[cfg_attr(kani, kani::modifies(self.buf, self.buf.buf))]
     pub fn append(&mut self, buf: &[u8]) {
         assert!(self.capacity() >= buf.len());
 
@@ -334,3 +373,127 @@ impl<'a> BorrowedCursor<'a> {
         self.buf.filled += buf.len();
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+
+    const N: usize = 8;
+
+    /// Wraps `storage` in a buffer whose filled and initialized lengths are arbitrary but
+    /// honest: the first `init` bytes of `storage` are written before `set_init` is called.
+    fn any_buf(storage: &mut [MaybeUninit<u8>; N]) -> BorrowedBuf<'_> {
+        let init = kani::any_where(|init: &usize| *init <= N);
+        let filled = kani::any_where(|filled: &usize| *filled <= init);
+        for byte in &mut storage[..init] {
+            byte.write(kani::any());
+        }
+        let mut buf = BorrowedBuf::from(&mut storage[..]);
+        unsafe { buf.set_init(init) };
+        buf.unfilled().advance(filled);
+        buf
+    }
+
+    #[kani::proof_for_contract(BorrowedBuf::set_init)]
+    fn check_buf_set_init() {
+        let mut storage = [MaybeUninit::uninit(); N];
+        let mut buf = any_buf(&mut storage);
+        unsafe { buf.set_init(kani::any()) };
+    }
+
+    #[kani::proof_for_contract(BorrowedCursor::set_init)]
+    fn check_cursor_set_init() {
+        let mut storage = [MaybeUninit::uninit(); N];
+        let mut buf = any_buf(&mut storage);
+        unsafe { buf.unfilled().set_init(kani::any()) };
+    }
+
+    #[kani::proof_for_contract(BorrowedCursor::advance_unchecked)]
+    fn check_advance_unchecked() {
+        let mut storage = [MaybeUninit::uninit(); N];
+        let mut buf = any_buf(&mut storage);
+        unsafe { buf.unfilled().advance_unchecked(kani::any()) };
+    }
+
+    #[kani::proof_for_contract(BorrowedCursor::as_mut)]
+    fn check_as_mut() {
+        let mut storage = [MaybeUninit::uninit(); N];
+        let mut buf = any_buf(&mut storage);
+        let _ = unsafe { buf.unfilled().as_mut() };
+    }
+
+    #[kani::proof_for_contract(BorrowedCursor::ensure_init)]
+    #[kani::unwind(9)]
+    fn check_ensure_init() {
+        let mut storage = [MaybeUninit::uninit(); N];
+        let mut buf = any_buf(&mut storage);
+        buf.unfilled().ensure_init();
+    }
+
+    #[kani::proof_for_contract(BorrowedCursor::append)]
+    #[kani::unwind(9)]
+    fn check_append() {
+        let mut storage = [MaybeUninit::uninit(); N];
+        let mut buf = any_buf(&mut storage);
+        let data: [u8; N] = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= buf.capacity() - buf.len());
+        buf.unfilled().append(&data[..len]);
+    }
+
+    /// Runs an arbitrary sequence of the safe buffer and cursor operations against a model of
+    /// what each byte was last written with, and checks that `filled()` and `init_ref()` only
+    /// ever expose bytes the model knows to be written, with the values written.
+    #[kani::proof]
+    #[kani::unwind(9)]
+    fn check_exposed_bytes_are_initialized() {
+        const STEPS: usize = 3;
+        let mut storage = [MaybeUninit::uninit(); N];
+        let mut buf = BorrowedBuf::from(&mut storage[..]);
+        let mut model: [Option<u8>; N] = [None; N];
+        for _ in 0..STEPS {
+            let (filled, init) = (buf.len(), buf.init_len());
+            match kani::any::<u8>() {
+                0 => {
+                    let data: [u8; N] = kani::any();
+                    let len = kani::any_where(|len: &usize| *len <= N - filled);
+                    buf.unfilled().append(&data[..len]);
+                    for i in 0..len {
+                        model[filled + i] = Some(data[i]);
+                    }
+                }
+                1 => {
+                    buf.unfilled().ensure_init();
+                    for byte in &mut model[init..] {
+                        *byte = Some(0);
+                    }
+                }
+                2 => {
+                    let n = kani::any_where(|n: &usize| *n <= init - filled);
+                    buf.unfilled().advance(n);
+                }
+                3 => {
+                    let value: u8 = kani::any();
+                    let len = kani::any_where(|len: &usize| *len <= N - init);
+                    let mut cursor = buf.unfilled();
+                    for byte in &mut cursor.uninit_mut()[..len] {
+                        byte.write(value);
+                    }
+                    unsafe { cursor.set_init(init - filled + len) };
+                    for byte in &mut model[init..init + len] {
+                        *byte = Some(value);
+                    }
+                }
+                _ => {
+                    buf.clear();
+                }
+            }
+            assert!(buf.is_safe());
+            let (filled, init) = (buf.len(), buf.init_len());
+            assert!((0..filled).all(|i| model[i] == Some(buf.filled()[i])));
+            let cursor = buf.unfilled();
+            assert!(cursor.is_safe());
+            assert!((filled..init).all(|i| model[i] == Some(cursor.init_ref()[i - filled])));
+        }
+    }
+}
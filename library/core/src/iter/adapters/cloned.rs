@@ -1,5 +1,7 @@
 use core::num::NonZero;
 
+use safety::requires;
+
 use crate::iter::adapters::zip::try_get_unchecked;
 use crate::iter::adapters::{SourceIter, TrustedRandomAccess, TrustedRandomAccessNoCoerce};
 use crate::iter::{FusedIterator, InPlaceIterable, TrustedLen, UncheckedIterator};
@@ -61,6 +63,7 @@ where
         self.it.map(T::clone).fold(init, f)
     }
 
+    #[requires(idx < self.size())]
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> T
     where
         Self: TrustedRandomAccessNoCoerce,
@@ -188,3 +191,22 @@ unsafe impl<I: InPlaceIterable> InPlaceIterable for Cloned<I> {
     const EXPAND_BY: Option<NonZero<usize>> = I::EXPAND_BY;
     const MERGE_BY: Option<NonZero<usize>> = I::MERGE_BY;
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::{kani, slice};
+
+    const MAX_LEN: usize = 8;
+
+    #[kani::proof_for_contract(<Cloned<slice::Iter<u8>> as Iterator>::__iterator_get_unchecked)]
+    fn check_get_unchecked() {
+        let arr: [u8; MAX_LEN] = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let mut it = arr[..len].iter().cloned();
+        let idx: usize = kani::any();
+        let x = unsafe { it.__iterator_get_unchecked(idx) };
+        assert!(x == arr[idx]);
+    }
+}
@@ -1,3 +1,5 @@
+use safety::requires;
+
 use crate::iter::adapters::zip::try_get_unchecked;
 use crate::iter::adapters::{SourceIter, TrustedRandomAccess, TrustedRandomAccessNoCoerce};
 use crate::iter::{FusedIterator, InPlaceIterable, TrustedFused, TrustedLen};
@@ -127,6 +129,7 @@ where
 
     #[rustc_inherit_overflow_checks]
     #[inline]
+    #[requires(idx < self.size())]
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> <Self as Iterator>::Item
     where
         Self: TrustedRandomAccessNoCoerce,
@@ -282,3 +285,29 @@ impl<I: Default> Default for Enumerate<I> {
         Enumerate::new(Default::default())
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::{kani, slice};
+
+    const MAX_LEN: usize = 8;
+
+    #[kani::proof_for_contract(<Enumerate<slice::Iter<u8>> as Iterator>::__iterator_get_unchecked)]
+    #[kani::unwind(9)]
+    fn check_get_unchecked() {
+        let arr: [u8; MAX_LEN] = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let mut it = arr[..len].iter().enumerate();
+        let skip = kani::any_where(|skip: &usize| *skip <= len);
+        // Advance through `next` so that `count` starts out non-zero.
+        for _ in 0..skip {
+            it.next();
+        }
+        let idx: usize = kani::any();
+        let (i, x) = unsafe { it.__iterator_get_unchecked(idx) };
+        assert!(i == skip + idx);
+        assert!(*x == arr[skip + idx]);
+    }
+}
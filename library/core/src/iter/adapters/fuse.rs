@@ -1,3 +1,5 @@
+use safety::requires;
+
 use crate::intrinsics;
 use crate::iter::adapters::SourceIter;
 use crate::iter::adapters::zip::try_get_unchecked;
@@ -109,6 +111,7 @@ where
     }
 
     #[inline]
+    #[requires(idx < self.size())]
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> Self::Item
     where
         Self: TrustedRandomAccessNoCoerce,
@@ -450,3 +453,22 @@ fn and_then_or_clear<T, U>(opt: &mut Option<T>, f: impl FnOnce(&mut T) -> Option
     }
     x
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::{kani, slice};
+
+    const MAX_LEN: usize = 8;
+
+    #[kani::proof_for_contract(<Fuse<slice::Iter<u8>> as Iterator>::__iterator_get_unchecked)]
+    fn check_get_unchecked() {
+        let arr: [u8; MAX_LEN] = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let mut it = arr[..len].iter().fuse();
+        let idx: usize = kani::any();
+        let x = unsafe { it.__iterator_get_unchecked(idx) };
+        assert!(*x == arr[idx]);
+    }
+}
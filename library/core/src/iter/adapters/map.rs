@@ -1,3 +1,5 @@
+use safety::requires;
+
 use crate::fmt;
 use crate::iter::adapters::zip::try_get_unchecked;
 use crate::iter::adapters::{SourceIter, TrustedRandomAccess, TrustedRandomAccessNoCoerce};
@@ -129,6 +131,7 @@ where
     }
 
     #[inline]
+    #[requires(idx < self.size())]
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> B
     where
         Self: TrustedRandomAccessNoCoerce,
@@ -239,3 +242,28 @@ unsafe impl<I: InPlaceIterable, F> InPlaceIterable for Map<I, F> {
     const EXPAND_BY: Option<NonZero<usize>> = I::EXPAND_BY;
     const MERGE_BY: Option<NonZero<usize>> = I::MERGE_BY;
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::{kani, slice};
+
+    const MAX_LEN: usize = 8;
+
+    fn widen(x: &u8) -> u16 {
+        *x as u16
+    }
+
+    #[kani::proof_for_contract(
+        <Map<slice::Iter<u8>, fn(&u8) -> u16> as Iterator>::__iterator_get_unchecked
+    )]
+    fn check_get_unchecked() {
+        let arr: [u8; MAX_LEN] = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let mut it = arr[..len].iter().map(widen as fn(&u8) -> u16);
+        let idx: usize = kani::any();
+        let x = unsafe { it.__iterator_get_unchecked(idx) };
+        assert!(x == arr[idx] as u16);
+    }
+}
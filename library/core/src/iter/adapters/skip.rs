@@ -1,3 +1,5 @@
+use safety::requires;
+
 use crate::intrinsics::unlikely;
 use crate::iter::adapters::SourceIter;
 use crate::iter::adapters::zip::try_get_unchecked;
@@ -158,6 +160,7 @@ where
     }
 
     #[doc(hidden)]
+    #[requires(idx < self.size())]
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> Self::Item
     where
         Self: TrustedRandomAccessNoCoerce,
@@ -287,3 +290,23 @@ where
 // I: TrustedLen would not.
 #[unstable(feature = "trusted_len", issue = "37572")]
 unsafe impl<I> TrustedLen for Skip<I> where I: Iterator + TrustedRandomAccess {}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::{kani, slice};
+
+    const MAX_LEN: usize = 8;
+
+    #[kani::proof_for_contract(<Skip<slice::Iter<u8>> as Iterator>::__iterator_get_unchecked)]
+    fn check_get_unchecked() {
+        let arr: [u8; MAX_LEN] = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let n = kani::any_where(|n: &usize| *n <= MAX_LEN);
+        let mut it = arr[..len].iter().skip(n);
+        let idx: usize = kani::any();
+        let x = unsafe { it.__iterator_get_unchecked(idx) };
+        assert!(*x == arr[n + idx]);
+    }
+}
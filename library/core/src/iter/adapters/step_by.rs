@@ -579,3 +579,53 @@ spec_int_ranges_r!(u8 u16 u32 usize);
 spec_int_ranges!(u8 u16 usize);
 #[cfg(target_pointer_width = "16")]
 spec_int_ranges_r!(u8 u16 usize);
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+
+    const MAX_LEN: usize = 8;
+
+    // `StepBy` is `TrustedLen` over `TrustedRandomAccess` iterators, which relies on its size
+    // hint being exact.
+    #[kani::proof]
+    #[kani::unwind(10)]
+    fn check_slice_size_hint_is_exact() {
+        let arr: [u8; MAX_LEN] = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let step = kani::any_where(|step: &usize| *step != 0);
+        let mut it = arr[..len].iter().step_by(step);
+        let mut i = 0;
+        loop {
+            let remaining = len.saturating_sub(i).div_ceil(step);
+            assert!(it.size_hint() == (remaining, Some(remaining)));
+            match it.next() {
+                Some(x) => assert!(*x == arr[i]),
+                None => break,
+            }
+            i += step;
+        }
+        assert!(i >= len);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(10)]
+    fn check_range_size_hint_is_exact() {
+        let start: u8 = kani::any();
+        let end = kani::any_where(|end: &u8| *end >= start && *end - start <= MAX_LEN as u8);
+        let step = kani::any_where(|step: &usize| *step != 0);
+        let mut it = (start..end).step_by(step);
+        let mut x = start as usize;
+        loop {
+            let remaining = (end as usize).saturating_sub(x).div_ceil(step);
+            assert!(it.size_hint() == (remaining, Some(remaining)));
+            match it.next() {
+                Some(y) => assert!(y as usize == x),
+                None => break,
+            }
+            x += step;
+        }
+    }
+}
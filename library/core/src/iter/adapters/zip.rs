@@ -1,9 +1,12 @@
+use safety::requires;
+
 use crate::cmp;
 use crate::fmt::{self, Debug};
 use crate::iter::{
     FusedIterator, InPlaceIterable, SourceIter, TrustedFused, TrustedLen, UncheckedIterator,
 };
 use crate::num::NonZero;
+use crate::ub_checks::Invariant;
 
 /// An iterator that iterates two other iterators simultaneously.
 ///
@@ -105,6 +108,7 @@ where
     }
 
     #[inline]
+    #[requires(idx < self.size())]
     unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> Self::Item
     where
         Self: TrustedRandomAccessNoCoerce,
@@ -413,6 +417,19 @@ where
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<A, B> Invariant for Zip<A, B>
+where
+    A: TrustedRandomAccess + Iterator,
+    B: TrustedRandomAccess + Iterator,
+{
+    /// The specialized `Zip` reads `a` and `b` at indices in `index..len`, and `a` alone at
+    /// indices in `len..a_len`. None of these may go past the end of `a`.
+    fn is_safe(&self) -> bool {
+        self.index <= self.len && self.len <= self.a_len && self.a_len <= self.a.size()
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<A, B> ExactSizeIterator for Zip<A, B>
 where
@@ -693,3 +710,59 @@ impl<A: TrustedLen, B: TrustedLen> SpecFold for Zip<A, B> {
         accum
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::{kani, ptr, slice};
+
+    const MAX_LEN: usize = 4;
+
+    #[kani::proof_for_contract(
+        <Zip<slice::Iter<u8>, slice::Iter<u16>> as Iterator>::__iterator_get_unchecked
+    )]
+    fn check_get_unchecked() {
+        let a: [u8; MAX_LEN] = kani::any();
+        let b: [u16; MAX_LEN] = kani::any();
+        let a_len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let b_len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let mut it = zip(a[..a_len].iter(), b[..b_len].iter());
+        let idx: usize = kani::any();
+        let (x, y) = unsafe { it.__iterator_get_unchecked(idx) };
+        assert!(ptr::eq(x, &a[idx]) && ptr::eq(y, &b[idx]));
+    }
+
+    /// Drives a zip of two slices of different lengths from both ends, checking that the
+    /// bookkeeping stays in bounds and that each pair comes from the right index.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_next_and_next_back() {
+        let a: [u8; MAX_LEN] = kani::any();
+        let b: [u16; MAX_LEN] = kani::any();
+        let a_len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let b_len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        let len = cmp::min(a_len, b_len);
+        let mut it = zip(a[..a_len].iter(), b[..b_len].iter());
+        assert!(it.is_safe());
+        let (mut front, mut back) = (0, 0);
+        for _ in 0..=MAX_LEN {
+            let from_front: bool = kani::any();
+            let item = if from_front { it.next() } else { it.next_back() };
+            assert!(it.is_safe());
+            match item {
+                Some((x, y)) => {
+                    let i = if from_front { front } else { len - 1 - back };
+                    assert!(ptr::eq(x, &a[i]) && ptr::eq(y, &b[i]));
+                    if from_front {
+                        front += 1;
+                    } else {
+                        back += 1;
+                    }
+                }
+                None => assert!(front + back == len),
+            }
+            assert!(it.size_hint() == (len - front - back, Some(len - front - back)));
+        }
+    }
+}
@@ -1,4 +1,9 @@
+use safety::{ensures, requires};
+
+#[cfg(kani)]
+use crate::kani;
 use crate::mem::MaybeUninit;
+use crate::ub_checks::Invariant;
 use crate::{fmt, str};
 
 /// Used for slow path in `Display` implementations when alignment is required.
@@ -14,6 +19,8 @@ impl<const SIZE: usize> DisplayBuffer<SIZE> {
     }
 
     #[inline]
+    #[requires(self.is_safe())]
+    #[ensures(|s: &&str| s.len() == self.len)]
     pub fn as_str(&self) -> &str {
         // SAFETY: `buf` is only written to by the `fmt::Write::write_str` implementation
         // which writes a valid UTF-8 string to `buf` and correctly sets `len`.
@@ -24,7 +31,25 @@ impl<const SIZE: usize> DisplayBuffer<SIZE> {
     }
 }
 
+#[unstable(feature = "ub_checks", issue = "none")]
+impl<const SIZE: usize> Invariant for DisplayBuffer<SIZE> {
+    /// The first `len` bytes of `buf` are initialized and hold valid UTF-8,
+    /// which is what lets `as_str` skip the UTF-8 check.
+    fn is_safe(&self) -> bool {
+        self.len <= SIZE
+            // SAFETY: `write_str` initializes every byte below `len`.
+            && str::from_utf8(unsafe { MaybeUninit::slice_assume_init_ref(&self.buf[..self.len]) })
+                .is_ok()
+    }
+}
+
 impl<const SIZE: usize> fmt::Write for DisplayBuffer<SIZE> {
+    // On success the bytes of `s` are appended whole, and a UTF-8 string
+    // followed by another one is still UTF-8; on failure nothing is written.
+    #[requires(self.is_safe())]
+    #[ensures(|result| self.is_safe()
+        && self.len == old(self.len) + if result.is_ok() { s.len() } else { 0 })]
+    #[cfg_attr(kani, kani::modifies(self))]
     fn write_str(&mut self, s: &str) -> fmt::Result {
         let bytes = s.as_bytes();
 
@@ -37,3 +62,46 @@ impl<const SIZE: usize> fmt::Write for DisplayBuffer<SIZE> {
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::fmt::Write;
+
+    const SIZE: usize = 6;
+
+    /// An arbitrary string of at most `N` bytes borrowed from `bytes`.
+    fn any_str<const N: usize>(bytes: &[u8; N]) -> &str {
+        let len: usize = kani::any_where(|len| *len <= N);
+        let s = str::from_utf8(&bytes[..len]);
+        kani::assume(s.is_ok());
+        s.unwrap()
+    }
+
+    /// A buffer that already holds an arbitrary string.
+    fn any_display_buffer(bytes: &[u8; SIZE]) -> DisplayBuffer<SIZE> {
+        let mut buf = DisplayBuffer::<SIZE>::new();
+        buf.write_str(any_str(bytes)).unwrap();
+        buf
+    }
+
+    #[kani::proof_for_contract(<DisplayBuffer<SIZE> as fmt::Write>::write_str)]
+    #[kani::unwind(8)]
+    fn check_write_str() {
+        let prefix: [u8; SIZE] = kani::any();
+        let mut buf = any_display_buffer(&prefix);
+        let bytes: [u8; SIZE] = kani::any();
+        let _ = buf.write_str(any_str(&bytes));
+    }
+
+    #[kani::proof_for_contract(DisplayBuffer::<SIZE>::as_str)]
+    #[kani::unwind(8)]
+    fn check_as_str() {
+        let bytes: [u8; SIZE] = kani::any();
+        let s = any_str(&bytes);
+        let mut buf = DisplayBuffer::<SIZE>::new();
+        buf.write_str(s).unwrap();
+        assert_eq!(buf.as_str(), s);
+    }
+}
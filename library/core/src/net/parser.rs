@@ -517,3 +517,86 @@ impl Error for AddrParseError {
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::fmt::Write;
+    use crate::kani;
+    use crate::net::display_buffer::DisplayBuffer;
+
+    /// Long enough for a full eight-group IPv6 address such as `1:2:3:4:5:6:7:8`
+    /// and for every IPv4 address.
+    const MAX_INPUT: usize = 16;
+
+    fn any_input(bytes: &[u8; MAX_INPUT]) -> &[u8] {
+        let len: usize = kani::any_where(|len| *len <= MAX_INPUT);
+        &bytes[..len]
+    }
+
+    macro_rules! check_parse_ascii {
+        ($($name:ident: $ty:ty,)*) => {$(
+            /// Parsing an arbitrary bounded byte string neither panics nor
+            /// indexes out of bounds, whether or not it is an address.
+            #[kani::proof]
+            #[kani::unwind(18)]
+            fn $name() {
+                let bytes: [u8; MAX_INPUT] = kani::any();
+                let _ = <$ty>::parse_ascii(any_input(&bytes));
+            }
+        )*};
+    }
+
+    check_parse_ascii! {
+        check_parse_ipv4: Ipv4Addr,
+        check_parse_ipv6: Ipv6Addr,
+        check_parse_ip: IpAddr,
+        check_parse_socket_v4: SocketAddrV4,
+        check_parse_socket_v6: SocketAddrV6,
+        check_parse_socket: SocketAddr,
+    }
+
+    /// Formats `addr` into a buffer of `SIZE` bytes and parses it back.
+    fn check_round_trip<T, const SIZE: usize>(addr: T)
+    where
+        T: fmt::Display + FromStr + PartialEq,
+    {
+        let mut buf = DisplayBuffer::<SIZE>::new();
+        write!(buf, "{addr}").unwrap();
+        assert!(buf.as_str().parse::<T>().ok() == Some(addr));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(12)]
+    fn check_ipv4_round_trip() {
+        let addr = Ipv4Addr::from_bits(kani::any());
+        check_round_trip::<_, { "255.255.255.255".len() }>(addr);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(12)]
+    fn check_ipv6_round_trip() {
+        let addr = Ipv6Addr::from(kani::any::<[u16; 8]>());
+        check_round_trip::<_, { "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff".len() }>(addr);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(12)]
+    fn check_socket_v4_round_trip() {
+        let addr = SocketAddrV4::new(Ipv4Addr::from_bits(kani::any()), kani::any());
+        check_round_trip::<_, { "255.255.255.255:65535".len() }>(addr);
+    }
+
+    /// `Display` does not print the flow info, so only addresses without one
+    /// can round-trip.
+    #[kani::proof]
+    #[kani::unwind(12)]
+    fn check_socket_v6_round_trip() {
+        let ip = Ipv6Addr::from(kani::any::<[u16; 8]>());
+        let addr = SocketAddrV6::new(ip, kani::any(), 0, kani::any());
+        const LONGEST_IPV6_SOCKET_ADDR: &str =
+            "[ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff%4294967295]:65535";
+        check_round_trip::<_, { LONGEST_IPV6_SOCKET_ADDR.len() }>(addr);
+    }
+}
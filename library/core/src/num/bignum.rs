@@ -117,6 +117,7 @@ macro_rules! define_bignum {
 
             /// Returns the `i`-th bit where bit 0 is the least significant one.
             /// In other words, the bit with weight `2^i`.
+            #[safety::requires(i < $n * <$ty>::BITS as usize)]
             pub fn get_bit(&self, i: usize) -> u8 {
                 let digitbits = <$ty>::BITS as usize;
                 let d = i / digitbits;
@@ -144,6 +145,10 @@ macro_rules! define_bignum {
             }
 
             /// Adds `other` to itself and returns its own mutable reference.
+            #[safety::requires(self.size <= $n && other.size <= $n)]
+            #[safety::requires(crate::cmp::max(self.bit_length(), other.bit_length()) < $n * <$ty>::BITS as usize)]
+            #[safety::ensures(|result| result.size <= $n)]
+            #[cfg_attr(kani, crate::kani::modifies(self))]
             pub fn add<'a>(&'a mut self, other: &$name) -> &'a mut $name {
                 use crate::{cmp, iter};
 
@@ -162,6 +167,9 @@ macro_rules! define_bignum {
                 self
             }
 
+            #[safety::requires(self.size <= $n && self.bit_length() < $n * <$ty>::BITS as usize)]
+            #[safety::ensures(|result| result.size <= $n)]
+            #[cfg_attr(kani, crate::kani::modifies(self))]
             pub fn add_small(&mut self, other: $ty) -> &mut $name {
                 let (v, mut carry) = self.base[0].carrying_add(other, false);
                 self.base[0] = v;
@@ -179,6 +187,9 @@ macro_rules! define_bignum {
             }
 
             /// Subtracts `other` from itself and returns its own mutable reference.
+            #[safety::requires(self.size <= $n && other.size <= $n && *self >= *other)]
+            #[safety::ensures(|result| result.size <= $n)]
+            #[cfg_attr(kani, crate::kani::modifies(self))]
             pub fn sub<'a>(&'a mut self, other: &$name) -> &'a mut $name {
                 use crate::{cmp, iter};
 
@@ -196,6 +207,12 @@ macro_rules! define_bignum {
 
             /// Multiplies itself by a digit-sized `other` and returns its own
             /// mutable reference.
+            #[safety::requires(self.size <= $n)]
+            #[safety::requires(
+                self.bit_length() + (<$ty>::BITS - other.leading_zeros()) as usize <= $n * <$ty>::BITS as usize
+            )]
+            #[safety::ensures(|result| result.size <= $n)]
+            #[cfg_attr(kani, crate::kani::modifies(self))]
             pub fn mul_small(&mut self, other: $ty) -> &mut $name {
                 let mut sz = self.size;
                 let mut carry = 0;
@@ -213,6 +230,10 @@ macro_rules! define_bignum {
             }
 
             /// Multiplies itself by `2^bits` and returns its own mutable reference.
+            #[safety::requires(bits / <$ty>::BITS as usize < $n && self.size + bits / <$ty>::BITS as usize <= $n)]
+            #[safety::requires(self.bit_length() + bits <= $n * <$ty>::BITS as usize)]
+            #[safety::ensures(|result| result.size <= $n)]
+            #[cfg_attr(kani, crate::kani::modifies(self))]
             pub fn mul_pow2(&mut self, bits: usize) -> &mut $name {
                 let digitbits = <$ty>::BITS as usize;
                 let digits = bits / digitbits;
@@ -252,6 +273,11 @@ macro_rules! define_bignum {
             }
 
             /// Multiplies itself by `5^e` and returns its own mutable reference.
+            // `5^e` needs fewer than `3 * e + 1` bits.
+            #[safety::requires(self.size <= $n && e <= $n * <$ty>::BITS as usize / 3)]
+            #[safety::requires(self.bit_length() + 3 * e < $n * <$ty>::BITS as usize)]
+            #[safety::ensures(|result| result.size <= $n)]
+            #[cfg_attr(kani, crate::kani::modifies(self))]
             pub fn mul_pow5(&mut self, mut e: usize) -> &mut $name {
                 use crate::mem;
                 use crate::num::bignum::SMALL_POW5;
@@ -281,6 +307,9 @@ macro_rules! define_bignum {
             /// Multiplies itself by a number described by `other[0] + other[1] * 2^W +
             /// other[2] * 2^(2W) + ...` (where `W` is the number of bits in the digit type)
             /// and returns its own mutable reference.
+            #[safety::requires(self.size + other.len() <= $n)]
+            #[safety::ensures(|result| result.size <= $n)]
+            #[cfg_attr(kani, crate::kani::modifies(self))]
             pub fn mul_digits<'a>(&'a mut self, other: &[$ty]) -> &'a mut $name {
                 // the internal routine. works best when aa.len() <= bb.len().
                 fn mul_inner(ret: &mut [$ty; $n], aa: &[$ty], bb: &[$ty]) -> usize {
@@ -322,6 +351,9 @@ macro_rules! define_bignum {
 
             /// Divides itself by a digit-sized `other` and returns its own
             /// mutable reference *and* the remainder.
+            #[safety::requires(other > 0 && self.size <= $n)]
+            #[safety::ensures(|(result, rem)| result.size <= $n && *rem < other)]
+            #[cfg_attr(kani, crate::kani::modifies(self))]
             pub fn div_rem_small(&mut self, other: $ty) -> (&mut $name, $ty) {
                 use crate::num::bignum::FullOps;
 
@@ -339,6 +371,9 @@ macro_rules! define_bignum {
 
             /// Divide self by another bignum, overwriting `q` with the quotient and `r` with the
             /// remainder.
+            #[safety::requires(!d.is_zero() && d.bit_length() < $n * <$ty>::BITS as usize)]
+            #[safety::requires(self.size <= $n && d.size <= $n)]
+            #[cfg_attr(kani, crate::kani::modifies(q, r))]
             pub fn div_rem(&self, d: &$name, q: &mut $name, r: &mut $name) {
                 // Stupid slow base-2 long division taken from
                 // https://en.wikipedia.org/wiki/Division_algorithm
@@ -430,3 +465,102 @@ define_bignum!(Big32x40: type=Digit32, n=40);
 pub mod tests {
     define_bignum!(Big8x3: type=u8, n=3);
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+
+    const DIGITS: usize = 40;
+    const MAX_USED: usize = 4;
+
+    /// Generates a `Big32x40` whose first `size` digits are arbitrary and the rest are zero.
+    ///
+    /// `size` is bounded by `MAX_USED` to keep the harnesses tractable.
+    fn any_big() -> Big32x40 {
+        let size = kani::any_where(|size: &usize| *size <= MAX_USED);
+        let mut base = [0; DIGITS];
+        for digit in &mut base[..size] {
+            *digit = kani::any();
+        }
+        Big32x40 { size, base }
+    }
+
+    #[kani::proof_for_contract(Big32x40::get_bit)]
+    fn check_get_bit() {
+        let x = any_big();
+        let _ = x.get_bit(kani::any());
+    }
+
+    #[kani::proof_for_contract(Big32x40::add)]
+    #[kani::unwind(41)]
+    fn check_add() {
+        let mut x = any_big();
+        let y = any_big();
+        let _ = x.add(&y);
+    }
+
+    #[kani::proof_for_contract(Big32x40::add_small)]
+    #[kani::unwind(41)]
+    fn check_add_small() {
+        let mut x = any_big();
+        let _ = x.add_small(kani::any());
+    }
+
+    #[kani::proof_for_contract(Big32x40::sub)]
+    #[kani::unwind(41)]
+    fn check_sub() {
+        let mut x = any_big();
+        let y = any_big();
+        let _ = x.sub(&y);
+    }
+
+    #[kani::proof_for_contract(Big32x40::mul_small)]
+    #[kani::unwind(41)]
+    fn check_mul_small() {
+        let mut x = any_big();
+        let _ = x.mul_small(kani::any());
+    }
+
+    #[kani::proof_for_contract(Big32x40::mul_pow2)]
+    #[kani::unwind(41)]
+    fn check_mul_pow2() {
+        let mut x = any_big();
+        let _ = x.mul_pow2(kani::any());
+    }
+
+    #[kani::proof_for_contract(Big32x40::mul_digits)]
+    #[kani::unwind(41)]
+    fn check_mul_digits() {
+        let mut x = any_big();
+        let other: [Digit32; MAX_USED] = kani::any();
+        let len = kani::any_where(|len: &usize| *len <= MAX_USED);
+        let _ = x.mul_digits(&other[..len]);
+    }
+
+    #[kani::proof_for_contract(Big32x40::div_rem_small)]
+    #[kani::unwind(41)]
+    fn check_div_rem_small() {
+        let mut x = any_big();
+        let _ = x.div_rem_small(kani::any());
+    }
+
+    #[kani::proof_for_contract(Big32x40::mul_pow5)]
+    #[kani::unwind(41)]
+    fn check_mul_pow5() {
+        let mut x = any_big();
+        let _ = x.mul_pow5(kani::any());
+    }
+
+    // The long division runs once per bit of `self`, so `self` is kept to a single digit here.
+    #[kani::proof_for_contract(Big32x40::div_rem)]
+    #[kani::unwind(41)]
+    fn check_div_rem() {
+        let x = Big32x40::from_u64(u64::from(kani::any::<Digit32>()));
+        let d = any_big();
+        let mut q = Big32x40::from_small(0);
+        let mut r = Big32x40::from_small(0);
+        x.div_rem(&d, &mut q, &mut r);
+    }
+}
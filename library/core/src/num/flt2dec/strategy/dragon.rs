@@ -4,12 +4,17 @@
 //! [^1]: Burger, R. G. and Dybvig, R. K. 1996. Printing floating-point numbers
 //!   quickly and accurately. SIGPLAN Not. 31, 5 (May. 1996), 108-116.
 
+use safety::{ensures, requires};
+
 use crate::cmp::Ordering;
 use crate::mem::MaybeUninit;
 use crate::num::bignum::{Big32x40 as Big, Digit32 as Digit};
 use crate::num::flt2dec::estimator::estimate_scaling_factor;
 use crate::num::flt2dec::{Decoded, MAX_SIG_DIGITS, round_up};
 
+#[cfg(kani)]
+use crate::kani;
+
 static POW10: [Digit; 10] =
     [1, 10, 100, 1000, 10000, 100000, 1000000, 10000000, 100000000, 1000000000];
 // precalculated arrays of `Digit`s for 5^(2^n).
@@ -27,6 +32,9 @@ static POW5TO256: [Digit; 19] = [
 ];
 
 #[doc(hidden)]
+// `10^n` needs at most `n * 10 / 3 + 1` bits, and the product has to fit into `Big`.
+#[requires(n < 512 && x.bit_length() + n * 10 / 3 + 1 <= 40 * Digit::BITS as usize)]
+#[cfg_attr(kani, kani::modifies(x))]
 pub fn mul_pow10(x: &mut Big, n: usize) -> &mut Big {
     debug_assert!(n < 512);
     // Save ourself the left shift for the smallest cases.
@@ -99,6 +107,13 @@ fn div_rem_upto_16<'a>(
 }
 
 /// The shortest mode implementation for Dragon.
+#[requires(d.mant > 0 && d.minus > 0 && d.plus > 0)]
+#[requires(d.mant.checked_add(d.plus).is_some() && d.mant.checked_sub(d.minus).is_some())]
+#[requires(buf.len() >= MAX_SIG_DIGITS)]
+#[ensures(|(digits, _)| !digits.is_empty()
+    && digits.len() <= MAX_SIG_DIGITS
+    && digits.iter().all(u8::is_ascii_digit))]
+#[cfg_attr(kani, kani::modifies(buf))]
 pub fn format_shortest<'a>(
     d: &Decoded,
     buf: &'a mut [MaybeUninit<u8>],
@@ -259,6 +274,10 @@ pub fn format_shortest<'a>(
 }
 
 /// The exact and fixed mode implementation for Dragon.
+#[requires(d.mant > 0 && d.minus > 0 && d.plus > 0)]
+#[requires(d.mant.checked_add(d.plus).is_some() && d.mant.checked_sub(d.minus).is_some())]
+#[ensures(|(digits, _)| digits.iter().all(u8::is_ascii_digit))]
+#[cfg_attr(kani, kani::modifies(buf))]
 pub fn format_exact<'a>(
     d: &Decoded,
     buf: &'a mut [MaybeUninit<u8>],
@@ -387,3 +406,46 @@ pub fn format_exact<'a>(
     // SAFETY: we initialized that memory above.
     (unsafe { MaybeUninit::slice_assume_init_ref(&buf[..len]) }, k)
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::num::flt2dec::decoder::any_decoded;
+
+    #[kani::proof_for_contract(mul_pow10)]
+    fn check_mul_pow10() {
+        let mut x = Big::from_u64(kani::any());
+        let _ = mul_pow10(&mut x, kani::any());
+    }
+
+    // The unwind bounds depend on the float type, see `any_decoded`.
+    macro_rules! check_dragon {
+        ($module:ident, $fty:ty, $unwind:literal) => {
+            mod $module {
+                use super::*;
+
+                #[kani::proof_for_contract(format_shortest)]
+                #[kani::unwind($unwind)]
+                fn check_format_shortest() {
+                    let d = any_decoded::<$fty>();
+                    let mut buf = [MaybeUninit::<u8>::uninit(); MAX_SIG_DIGITS];
+                    let _ = format_shortest(&d, &mut buf);
+                }
+
+                #[kani::proof_for_contract(format_exact)]
+                #[kani::unwind($unwind)]
+                fn check_format_exact() {
+                    const MAX_LEN: usize = 8;
+                    let d = any_decoded::<$fty>();
+                    let mut buf = [MaybeUninit::<u8>::uninit(); MAX_LEN];
+                    let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+                    let _ = format_exact(&d, &mut buf[..len], kani::any());
+                }
+            }
+        };
+    }
+
+    check_dragon!(verify_f32, f32, 9);
+    check_dragon!(verify_f64, f64, 41);
+}
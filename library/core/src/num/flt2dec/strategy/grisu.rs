@@ -5,10 +5,15 @@
 //! [^1]: Florian Loitsch. 2010. Printing floating-point numbers quickly and
 //!   accurately with integers. SIGPLAN Not. 45, 6 (June 2010), 233-243.
 
+use safety::{ensures, requires};
+
 use crate::mem::MaybeUninit;
 use crate::num::diy_float::Fp;
 use crate::num::flt2dec::{Decoded, MAX_SIG_DIGITS, round_up};
 
+#[cfg(kani)]
+use crate::kani;
+
 // see the comments in `format_shortest_opt` for the rationale.
 #[doc(hidden)]
 pub const ALPHA: i16 = -60;
@@ -162,6 +167,14 @@ pub fn max_pow10_no_more_than(x: u32) -> (u8, u32) {
 /// The shortest mode implementation for Grisu.
 ///
 /// It returns `None` when it would return an inexact representation otherwise.
+#[requires(d.mant > 0 && d.minus > 0 && d.plus > 0)]
+#[requires(d.mant.checked_add(d.plus).is_some_and(|high| high < (1 << 61)))]
+#[requires(d.mant.checked_sub(d.minus).is_some())]
+#[requires(buf.len() >= MAX_SIG_DIGITS)]
+#[ensures(|result| result.map_or(true, |(digits, _)| !digits.is_empty()
+    && digits.len() <= MAX_SIG_DIGITS
+    && digits.iter().all(u8::is_ascii_digit)))]
+#[cfg_attr(kani, kani::modifies(buf))]
 pub fn format_shortest_opt<'a>(
     d: &Decoded,
     buf: &'a mut [MaybeUninit<u8>],
@@ -468,6 +481,10 @@ pub fn format_shortest<'a>(
 /// The exact and fixed mode implementation for Grisu.
 ///
 /// It returns `None` when it would return an inexact representation otherwise.
+#[requires(d.mant > 0 && d.mant < (1 << 61))]
+#[requires(!buf.is_empty())]
+#[ensures(|result| result.map_or(true, |(digits, _)| digits.iter().all(u8::is_ascii_digit)))]
+#[cfg_attr(kani, kani::modifies(buf))]
 pub fn format_exact_opt<'a>(
     d: &Decoded,
     buf: &'a mut [MaybeUninit<u8>],
@@ -631,130 +648,135 @@ pub fn format_exact_opt<'a>(
     }
 
     // further calculation is useless (`possibly_round` definitely fails), so we give up.
-    return None;
+    None
+}
 
-    // we've generated all requested digits of `v`, which should be also same to corresponding
-    // digits of `v - 1 ulp`. now we check if there is a unique representation shared by
-    // both `v - 1 ulp` and `v + 1 ulp`; this can be either same to generated digits, or
-    // to the rounded-up version of those digits. if the range contains multiple representations
-    // of the same length, we cannot be sure and should return `None` instead.
+/// we've generated all requested digits of `v`, which should be also same to corresponding
+/// digits of `v - 1 ulp`. now we check if there is a unique representation shared by
+/// both `v - 1 ulp` and `v + 1 ulp`; this can be either same to generated digits, or
+/// to the rounded-up version of those digits. if the range contains multiple representations
+/// of the same length, we cannot be sure and should return `None` instead.
+///
+/// all arguments here are scaled by the common (but implicit) value `k`, so that:
+/// - `remainder = (v % 10^kappa) * k`
+/// - `ten_kappa = 10^kappa * k`
+/// - `ulp = 2^-e * k`
+///
+/// # Safety
+///
+/// The first `len` bytes of `buf` must be initialized.
+#[requires(len <= buf.len() && remainder < ten_kappa)]
+#[ensures(|result| result.map_or(true, |(digits, _)| digits.iter().all(u8::is_ascii_digit)))]
+#[cfg_attr(kani, kani::modifies(buf))]
+unsafe fn possibly_round(
+    buf: &mut [MaybeUninit<u8>],
+    mut len: usize,
+    mut exp: i16,
+    limit: i16,
+    remainder: u64,
+    ten_kappa: u64,
+    ulp: u64,
+) -> Option<(&[u8], i16)> {
+    debug_assert!(remainder < ten_kappa);
+
+    //           10^kappa
+    //    :   :   :<->:   :
+    //    :   :   :   :   :
+    //    :|1 ulp|1 ulp|  :
+    //    :|<--->|<--->|  :
+    // ----|-----|-----|----
+    //     |     v     |
+    // v - 1 ulp   v + 1 ulp
     //
-    // all arguments here are scaled by the common (but implicit) value `k`, so that:
-    // - `remainder = (v % 10^kappa) * k`
-    // - `ten_kappa = 10^kappa * k`
-    // - `ulp = 2^-e * k`
+    // (for the reference, the dotted line indicates the exact value for
+    // possible representations in given number of digits.)
     //
-    // SAFETY: the first `len` bytes of `buf` must be initialized.
-    unsafe fn possibly_round(
-        buf: &mut [MaybeUninit<u8>],
-        mut len: usize,
-        mut exp: i16,
-        limit: i16,
-        remainder: u64,
-        ten_kappa: u64,
-        ulp: u64,
-    ) -> Option<(&[u8], i16)> {
-        debug_assert!(remainder < ten_kappa);
-
-        //           10^kappa
-        //    :   :   :<->:   :
-        //    :   :   :   :   :
-        //    :|1 ulp|1 ulp|  :
-        //    :|<--->|<--->|  :
-        // ----|-----|-----|----
-        //     |     v     |
-        // v - 1 ulp   v + 1 ulp
-        //
-        // (for the reference, the dotted line indicates the exact value for
-        // possible representations in given number of digits.)
-        //
-        // error is too large that there are at least three possible representations
-        // between `v - 1 ulp` and `v + 1 ulp`. we cannot determine which one is correct.
-        if ulp >= ten_kappa {
-            return None;
-        }
+    // error is too large that there are at least three possible representations
+    // between `v - 1 ulp` and `v + 1 ulp`. we cannot determine which one is correct.
+    if ulp >= ten_kappa {
+        return None;
+    }
 
-        //    10^kappa
-        //   :<------->:
-        //   :         :
-        //   : |1 ulp|1 ulp|
-        //   : |<--->|<--->|
-        // ----|-----|-----|----
-        //     |     v     |
-        // v - 1 ulp   v + 1 ulp
-        //
-        // in fact, 1/2 ulp is enough to introduce two possible representations.
-        // (remember that we need a unique representation for both `v - 1 ulp` and `v + 1 ulp`.)
-        // this won't overflow, as `ulp < ten_kappa` from the first check.
-        if ten_kappa - ulp <= ulp {
-            return None;
-        }
+    //    10^kappa
+    //   :<------->:
+    //   :         :
+    //   : |1 ulp|1 ulp|
+    //   : |<--->|<--->|
+    // ----|-----|-----|----
+    //     |     v     |
+    // v - 1 ulp   v + 1 ulp
+    //
+    // in fact, 1/2 ulp is enough to introduce two possible representations.
+    // (remember that we need a unique representation for both `v - 1 ulp` and `v + 1 ulp`.)
+    // this won't overflow, as `ulp < ten_kappa` from the first check.
+    if ten_kappa - ulp <= ulp {
+        return None;
+    }
 
-        //     remainder
-        //       :<->|                           :
-        //       :   |                           :
-        //       :<--------- 10^kappa ---------->:
-        //     | :   |                           :
-        //     |1 ulp|1 ulp|                     :
-        //     |<--->|<--->|                     :
-        // ----|-----|-----|------------------------
-        //     |     v     |
-        // v - 1 ulp   v + 1 ulp
-        //
-        // if `v + 1 ulp` is closer to the rounded-down representation (which is already in `buf`),
-        // then we can safely return. note that `v - 1 ulp` *can* be less than the current
-        // representation, but as `1 ulp < 10^kappa / 2`, this condition is enough:
-        // the distance between `v - 1 ulp` and the current representation
-        // cannot exceed `10^kappa / 2`.
-        //
-        // the condition equals to `remainder + ulp < 10^kappa / 2`.
-        // since this can easily overflow, first check if `remainder < 10^kappa / 2`.
-        // we've already verified that `ulp < 10^kappa / 2`, so as long as
-        // `10^kappa` did not overflow after all, the second check is fine.
-        if ten_kappa - remainder > remainder && ten_kappa - 2 * remainder >= 2 * ulp {
-            // SAFETY: our caller initialized that memory.
-            return Some((unsafe { MaybeUninit::slice_assume_init_ref(&buf[..len]) }, exp));
-        }
+    //     remainder
+    //       :<->|                           :
+    //       :   |                           :
+    //       :<--------- 10^kappa ---------->:
+    //     | :   |                           :
+    //     |1 ulp|1 ulp|                     :
+    //     |<--->|<--->|                     :
+    // ----|-----|-----|------------------------
+    //     |     v     |
+    // v - 1 ulp   v + 1 ulp
+    //
+    // if `v + 1 ulp` is closer to the rounded-down representation (which is already in `buf`),
+    // then we can safely return. note that `v - 1 ulp` *can* be less than the current
+    // representation, but as `1 ulp < 10^kappa / 2`, this condition is enough:
+    // the distance between `v - 1 ulp` and the current representation
+    // cannot exceed `10^kappa / 2`.
+    //
+    // the condition equals to `remainder + ulp < 10^kappa / 2`.
+    // since this can easily overflow, first check if `remainder < 10^kappa / 2`.
+    // we've already verified that `ulp < 10^kappa / 2`, so as long as
+    // `10^kappa` did not overflow after all, the second check is fine.
+    if ten_kappa - remainder > remainder && ten_kappa - 2 * remainder >= 2 * ulp {
+        // SAFETY: our caller initialized that memory.
+        return Some((unsafe { MaybeUninit::slice_assume_init_ref(&buf[..len]) }, exp));
+    }
 
-        //   :<------- remainder ------>|   :
-        //   :                          |   :
-        //   :<--------- 10^kappa --------->:
-        //   :                    |     |   : |
-        //   :                    |1 ulp|1 ulp|
-        //   :                    |<--->|<--->|
-        // -----------------------|-----|-----|-----
-        //                        |     v     |
-        //                    v - 1 ulp   v + 1 ulp
-        //
-        // on the other hands, if `v - 1 ulp` is closer to the rounded-up representation,
-        // we should round up and return. for the same reason we don't need to check `v + 1 ulp`.
-        //
-        // the condition equals to `remainder - ulp >= 10^kappa / 2`.
-        // again we first check if `remainder > ulp` (note that this is not `remainder >= ulp`,
-        // as `10^kappa` is never zero). also note that `remainder - ulp <= 10^kappa`,
-        // so the second check does not overflow.
-        if remainder > ulp && ten_kappa - (remainder - ulp) <= remainder - ulp {
-            if let Some(c) =
-                // SAFETY: our caller must have initialized that memory.
-                round_up(unsafe { MaybeUninit::slice_assume_init_mut(&mut buf[..len]) })
-            {
-                // only add an additional digit when we've been requested the fixed precision.
-                // we also need to check that, if the original buffer was empty,
-                // the additional digit can only be added when `exp == limit` (edge case).
-                exp += 1;
-                if exp > limit && len < buf.len() {
-                    buf[len] = MaybeUninit::new(c);
-                    len += 1;
-                }
+    //   :<------- remainder ------>|   :
+    //   :                          |   :
+    //   :<--------- 10^kappa --------->:
+    //   :                    |     |   : |
+    //   :                    |1 ulp|1 ulp|
+    //   :                    |<--->|<--->|
+    // -----------------------|-----|-----|-----
+    //                        |     v     |
+    //                    v - 1 ulp   v + 1 ulp
+    //
+    // on the other hands, if `v - 1 ulp` is closer to the rounded-up representation,
+    // we should round up and return. for the same reason we don't need to check `v + 1 ulp`.
+    //
+    // the condition equals to `remainder - ulp >= 10^kappa / 2`.
+    // again we first check if `remainder > ulp` (note that this is not `remainder >= ulp`,
+    // as `10^kappa` is never zero). also note that `remainder - ulp <= 10^kappa`,
+    // so the second check does not overflow.
+    if remainder > ulp && ten_kappa - (remainder - ulp) <= remainder - ulp {
+        if let Some(c) =
+            // SAFETY: our caller must have initialized that memory.
+            round_up(unsafe { MaybeUninit::slice_assume_init_mut(&mut buf[..len]) })
+        {
+            // only add an additional digit when we've been requested the fixed precision.
+            // we also need to check that, if the original buffer was empty,
+            // the additional digit can only be added when `exp == limit` (edge case).
+            exp += 1;
+            if exp > limit && len < buf.len() {
+                buf[len] = MaybeUninit::new(c);
+                len += 1;
             }
-            // SAFETY: we and our caller initialized that memory.
-            return Some((unsafe { MaybeUninit::slice_assume_init_ref(&buf[..len]) }, exp));
         }
-
-        // otherwise we are doomed (i.e., some values between `v - 1 ulp` and `v + 1 ulp` are
-        // rounding down and others are rounding up) and give up.
-        None
+        // SAFETY: we and our caller initialized that memory.
+        return Some((unsafe { MaybeUninit::slice_assume_init_ref(&buf[..len]) }, exp));
     }
+
+    // otherwise we are doomed (i.e., some values between `v - 1 ulp` and `v + 1 ulp` are
+    // rounding down and others are rounding up) and give up.
+    None
 }
 
 /// The exact and fixed mode implementation for Grisu with Dragon fallback.
@@ -774,3 +796,74 @@ pub fn format_exact<'a>(
         None => fallback(d, buf, limit),
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::num::flt2dec::decoder::any_decoded;
+
+    #[kani::proof_for_contract(possibly_round)]
+    #[kani::unwind(9)]
+    fn check_possibly_round() {
+        const MAX_LEN: usize = 8;
+        let mut buf = [MaybeUninit::<u8>::uninit(); MAX_LEN];
+        let len = kani::any_where(|len: &usize| *len <= MAX_LEN);
+        for c in &mut buf[..len] {
+            *c = MaybeUninit::new(kani::any_where(u8::is_ascii_digit));
+        }
+        let _ = unsafe {
+            possibly_round(
+                &mut buf,
+                len,
+                kani::any(),
+                kani::any(),
+                kani::any(),
+                kani::any(),
+                kani::any(),
+            )
+        };
+    }
+
+    // `format_exact` falls back to Dragon, so the unwind bounds are Dragon's, see `any_decoded`.
+    macro_rules! check_grisu {
+        ($module:ident, $fty:ty, $unwind:literal) => {
+            mod $module {
+                use super::*;
+
+                // Reading every returned digit in the postcondition also checks that the
+                // slice handed out by `slice_assume_init_ref` was fully initialized.
+                #[kani::proof_for_contract(format_shortest_opt)]
+                fn check_format_shortest_opt() {
+                    let d = any_decoded::<$fty>();
+                    let mut buf = [MaybeUninit::<u8>::uninit(); MAX_SIG_DIGITS];
+                    let _ = format_shortest_opt(&d, &mut buf);
+                }
+
+                #[kani::proof_for_contract(format_exact_opt)]
+                fn check_format_exact_opt() {
+                    const MAX_LEN: usize = 8;
+                    let d = any_decoded::<$fty>();
+                    let mut buf = [MaybeUninit::<u8>::uninit(); MAX_LEN];
+                    let len = kani::any_where(|len: &usize| *len > 0 && *len <= MAX_LEN);
+                    let _ = format_exact_opt(&d, &mut buf[..len], kani::any());
+                }
+
+                #[kani::proof]
+                #[kani::unwind($unwind)]
+                fn check_format_exact_in_bounds() {
+                    const MAX_LEN: usize = 8;
+                    let d = any_decoded::<$fty>();
+                    let mut buf = [MaybeUninit::<u8>::uninit(); MAX_LEN];
+                    let len = kani::any_where(|len: &usize| *len > 0 && *len <= MAX_LEN);
+                    let (digits, _) = format_exact(&d, &mut buf[..len], kani::any());
+                    assert!(digits.len() <= len);
+                    assert!(digits.iter().all(u8::is_ascii_digit));
+                }
+            }
+        };
+    }
+
+    check_grisu!(verify_f32, f32, 9);
+    check_grisu!(verify_f64, f64, 41);
+}
@@ -7,10 +7,12 @@ use crate::marker::{Freeze, StructuralPartialEq};
 use crate::ops::{BitOr, BitOrAssign, Div, DivAssign, Neg, Rem, RemAssign};
 use crate::panic::{RefUnwindSafe, UnwindSafe};
 use crate::str::FromStr;
+use crate::ub_checks::Invariant;
 use crate::{fmt, intrinsics, ptr, ub_checks};
 use safety::{ensures, requires};
 #[cfg(kani)]
 use crate::kani;
+
 /// A marker trait for primitive types which can be zero.
 ///
 /// This is an implementation detail for <code>[NonZero]\<T></code> which may disappear or be replaced at any time.
@@ -427,6 +429,18 @@ where
     #[unstable(feature = "nonzero_from_mut", issue = "106290")]
     #[must_use]
     #[inline]
+    #[requires({
+        let size = core::mem::size_of::<T>();
+        let ptr = n as *const T as *const u8;
+        let slice = unsafe { core::slice::from_raw_parts(ptr, size) };
+        !slice.iter().all(|&byte| byte == 0)
+    })]
+    #[ensures(|result: &&mut Self| {
+        let size = core::mem::size_of::<T>();
+        let ptr = *result as *const Self as *const u8;
+        let slice = unsafe { core::slice::from_raw_parts(ptr, size) };
+        !slice.iter().all(|&byte| byte == 0)
+    })]
     pub unsafe fn from_mut_unchecked(n: &mut T) -> &mut Self {
         match Self::from_mut(n) {
             Some(n) => n,
@@ -514,6 +528,15 @@ macro_rules! nonzero_integer {
         #[$stability]
         pub type $Ty = NonZero<$Int>;
 
+        #[unstable(feature = "ub_checks", issue = "none")]
+        impl Invariant for NonZero<$Int> {
+            /// The niche optimisation relies on the wrapped value never being zero.
+            #[inline(always)]
+            fn is_safe(&self) -> bool {
+                self.get() != 0
+            }
+        }
+
         impl NonZero<$Int> {
             /// The size of this non-zero integer type in bits.
             ///
@@ -553,6 +576,7 @@ macro_rules! nonzero_integer {
             #[must_use = "this returns the result of the operation, \
                           without modifying the original"]
             #[inline]
+            #[ensures(|result| *result < Self::BITS && *result == self.get().leading_zeros())]
             pub const fn leading_zeros(self) -> u32 {
                 // SAFETY: since `self` cannot be zero, it is safe to call `ctlz_nonzero`.
                 unsafe {
@@ -922,6 +946,7 @@ macro_rules! nonzero_integer {
             #[must_use = "this returns the result of the operation, \
                           without modifying the original"]
             #[inline]
+            #[ensures(|result| result.map(|r| r.get()) == self.get().checked_mul(other.get()))]
             pub const fn checked_mul(self, other: Self) -> Option<Self> {
                 if let Some(result) = self.get().checked_mul(other.get()) {
                     // SAFETY:
@@ -1009,6 +1034,8 @@ macro_rules! nonzero_integer {
             #[must_use = "this returns the result of the operation, \
                           without modifying the original"]
             #[inline]
+            #[requires(!self.get().overflowing_mul(other.get()).1)]
+            #[ensures(|result| result.is_safe() && result.get() == self.get().wrapping_mul(other.get()))]
             pub const unsafe fn unchecked_mul(self, other: Self) -> Self {
                 // SAFETY: The caller ensures there is no overflow.
                 unsafe { Self::new_unchecked(self.get().unchecked_mul(other.get())) }
@@ -1087,6 +1114,7 @@ macro_rules! nonzero_integer {
             #[must_use = "this returns the result of the operation, \
                           without modifying the original"]
             #[inline]
+            #[ensures(|result| result.is_safe() && result.get() == self.get().saturating_pow(other))]
             pub const fn saturating_pow(self, other: u32) -> Self {
                 // SAFETY:
                 // - `saturating_pow` returns `u*::MAX`/`i*::MAX`/`i*::MIN` on overflow/underflow,
@@ -1371,6 +1399,8 @@ macro_rules! nonzero_integer_signedness_dependent_methods {
         #[must_use = "this returns the result of the operation, \
                       without modifying the original"]
         #[inline]
+        #[requires(!self.get().overflowing_add(other).1)]
+        #[ensures(|result| result.is_safe() && result.get() == self.get().wrapping_add(other))]
         pub const unsafe fn unchecked_add(self, other: $Int) -> Self {
             // SAFETY: The caller ensures there is no overflow.
             unsafe { Self::new_unchecked(self.get().unchecked_add(other)) }
@@ -1439,6 +1469,7 @@ macro_rules! nonzero_integer_signedness_dependent_methods {
         #[must_use = "this returns the result of the operation, \
                       without modifying the original"]
         #[inline]
+        #[ensures(|result| *result < Self::BITS && self.get() >> *result == 1)]
         pub const fn ilog2(self) -> u32 {
             Self::BITS - 1 - self.leading_zeros()
         }
@@ -1565,6 +1596,7 @@ macro_rules! nonzero_integer_signedness_dependent_methods {
         #[must_use = "this returns the result of the operation, \
                       without modifying the original"]
         #[inline]
+        #[ensures(|result| result.is_safe() && result.get() == self.get().isqrt())]
         pub const fn isqrt(self) -> Self {
             let result = self.get().isqrt();
 
@@ -2214,4 +2246,131 @@ mod verify {
     nonzero_check!(u64, core::num::NonZeroU64, nonzero_check_new_unchecked_for_u64);
     nonzero_check!(u128, core::num::NonZeroU128, nonzero_check_new_unchecked_for_u128);
     nonzero_check!(usize, core::num::NonZeroUsize, nonzero_check_new_unchecked_for_usize);
+
+    /// Generates a non-zero value of type `$t` together with the harnesses that are shared
+    /// between signed and unsigned types.
+    macro_rules! nonzero_check_common {
+        ($t:ty, $module:ident) => {
+            mod $module {
+                use super::*;
+
+                fn any_nonzero() -> NonZero<$t> {
+                    let x: $t = kani::any_where(|x: &$t| *x != 0);
+                    NonZero::new(x).unwrap()
+                }
+
+                /// The unchecked constructors and the arithmetic methods only ever produce
+                /// values that uphold the invariant.
+                #[kani::proof]
+                pub fn check_invariant() {
+                    let x: $t = kani::any_where(|x: &$t| *x != 0);
+                    assert!(unsafe { NonZero::<$t>::new_unchecked(x) }.is_safe());
+                    let mut y = x;
+                    assert!(unsafe { NonZero::<$t>::from_mut_unchecked(&mut y) }.is_safe());
+
+                    let (a, b) = (any_nonzero(), any_nonzero());
+                    assert!(a.saturating_mul(b).is_safe());
+                    assert!(a.saturating_pow(kani::any()).is_safe());
+                    if let Some(product) = a.checked_mul(b) {
+                        assert!(product.is_safe());
+                        assert!(unsafe { a.unchecked_mul(b) }.is_safe());
+                    }
+                }
+
+                #[kani::proof_for_contract(NonZero::<$t>::from_mut_unchecked)]
+                pub fn check_from_mut_unchecked() {
+                    let mut x: $t = kani::any();
+                    unsafe {
+                        NonZero::<$t>::from_mut_unchecked(&mut x);
+                    }
+                }
+
+                #[kani::proof_for_contract(NonZero::<$t>::unchecked_mul)]
+                pub fn check_unchecked_mul() {
+                    let x = any_nonzero();
+                    let y = any_nonzero();
+                    unsafe {
+                        x.unchecked_mul(y);
+                    }
+                }
+
+                #[kani::proof_for_contract(NonZero::<$t>::checked_mul)]
+                pub fn check_checked_mul() {
+                    let _ = any_nonzero().checked_mul(any_nonzero());
+                }
+
+                #[kani::proof_for_contract(NonZero::<$t>::saturating_pow)]
+                pub fn check_saturating_pow() {
+                    let _ = any_nonzero().saturating_pow(kani::any());
+                }
+
+                #[kani::proof_for_contract(NonZero::<$t>::leading_zeros)]
+                pub fn check_leading_zeros() {
+                    let _ = any_nonzero().leading_zeros();
+                }
+            }
+        };
+    }
+
+    /// Harnesses for the methods that only exist on unsigned non-zero types.
+    macro_rules! nonzero_check_unsigned {
+        ($t:ty, $module:ident) => {
+            mod $module {
+                use super::*;
+
+                fn any_nonzero() -> NonZero<$t> {
+                    let x: $t = kani::any_where(|x: &$t| *x != 0);
+                    NonZero::new(x).unwrap()
+                }
+
+                /// The additions of a non-zero value to an unsigned one stay non-zero.
+                #[kani::proof]
+                pub fn check_add_invariant() {
+                    let (a, b) = (any_nonzero(), kani::any::<$t>());
+                    assert!(a.saturating_add(b).is_safe());
+                    if let Some(sum) = a.checked_add(b) {
+                        assert!(sum.is_safe());
+                        assert!(unsafe { a.unchecked_add(b) }.is_safe());
+                    }
+                }
+
+                #[kani::proof_for_contract(NonZero::<$t>::unchecked_add)]
+                pub fn check_unchecked_add() {
+                    unsafe {
+                        any_nonzero().unchecked_add(kani::any());
+                    }
+                }
+
+                #[kani::proof_for_contract(NonZero::<$t>::ilog2)]
+                pub fn check_ilog2() {
+                    let _ = any_nonzero().ilog2();
+                }
+
+                #[kani::proof_for_contract(NonZero::<$t>::isqrt)]
+                pub fn check_isqrt() {
+                    let _ = any_nonzero().isqrt();
+                }
+            }
+        };
+    }
+
+    nonzero_check_common!(i8, nonzero_check_i8);
+    nonzero_check_common!(i16, nonzero_check_i16);
+    nonzero_check_common!(i32, nonzero_check_i32);
+    nonzero_check_common!(i64, nonzero_check_i64);
+    nonzero_check_common!(i128, nonzero_check_i128);
+    nonzero_check_common!(isize, nonzero_check_isize);
+    nonzero_check_common!(u8, nonzero_check_u8);
+    nonzero_check_common!(u16, nonzero_check_u16);
+    nonzero_check_common!(u32, nonzero_check_u32);
+    nonzero_check_common!(u64, nonzero_check_u64);
+    nonzero_check_common!(u128, nonzero_check_u128);
+    nonzero_check_common!(usize, nonzero_check_usize);
+
+    nonzero_check_unsigned!(u8, nonzero_check_unsigned_u8);
+    nonzero_check_unsigned!(u16, nonzero_check_unsigned_u16);
+    nonzero_check_unsigned!(u32, nonzero_check_unsigned_u32);
+    nonzero_check_unsigned!(u64, nonzero_check_unsigned_u64);
+    nonzero_check_unsigned!(u128, nonzero_check_unsigned_u128);
+    nonzero_check_unsigned!(usize, nonzero_check_unsigned_usize);
 }
@@ -6,6 +6,10 @@
 //! for pivot selection. Using this as a fallback ensures O(n) worst case running time with
 //! better performance than one would get using heapsort as fallback.
 
+use safety::{ensures, requires};
+
+#[cfg(kani)]
+use crate::kani;
 use crate::mem::{self, SizedTypeProperties};
 #[cfg(not(feature = "optimize_for_size"))]
 use crate::slice::sort::shared::pivot::choose_pivot;
@@ -13,6 +17,9 @@ use crate::slice::sort::shared::smallsort::insertion_sort_shift_left;
 use crate::slice::sort::unstable::quicksort::partition;
 
 /// Reorders the slice such that the element at `index` is at its final sorted position.
+#[ensures(|(left, _, right)| left.len() == index && right.len() == old(v.len()) - index - 1)]
+#[ensures(|(left, pivot, right)| is_partitioned_by(left, pivot, right, is_less))]
+#[cfg_attr(kani, kani::modifies(v))]
 pub(crate) fn partition_at_index<T, F>(
     v: &mut [T],
     index: usize,
@@ -142,6 +149,20 @@ fn partition_at_index_loop<'a, T, F>(
     }
 }
 
+/// Returns `true` if `is_less` orders no element of `left` after `pivot`, and no element of `right`
+/// before it.
+///
+/// This is what `partition_at_index` promises when `is_less` is a strict weak order.
+#[cfg(kani)]
+fn is_partitioned_by<T, F: FnMut(&T, &T) -> bool>(
+    left: &[T],
+    pivot: &T,
+    right: &[T],
+    mut is_less: F,
+) -> bool {
+    left.iter().all(|x| !is_less(pivot, x)) && right.iter().all(|x| !is_less(x, pivot))
+}
+
 /// Helper function that returns the index of the minimum element in the slice using the given
 /// comparator function
 fn min_index<T, F: FnMut(&T, &T) -> bool>(slice: &[T], is_less: &mut F) -> Option<usize> {
@@ -164,6 +185,8 @@ fn max_index<T, F: FnMut(&T, &T) -> bool>(slice: &[T], is_less: &mut F) -> Optio
 
 /// Selection algorithm to select the k-th element from the slice in guaranteed O(n) time.
 /// This is essentially a quickselect that uses Tukey's Ninther for pivot selection
+#[requires(k < v.len())]
+#[cfg_attr(kani, kani::modifies(v))]
 fn median_of_medians<T, F: FnMut(&T, &T) -> bool>(mut v: &mut [T], is_less: &mut F, mut k: usize) {
     // Since this function isn't public, it should never be called with an out-of-bounds index.
     debug_assert!(k < v.len());
@@ -215,6 +238,9 @@ fn median_of_medians<T, F: FnMut(&T, &T) -> bool>(mut v: &mut [T], is_less: &mut
 // Optimized for when `k` lies somewhere in the middle of the slice. Selects a pivot
 // as close as possible to the median of the slice. For more details on how the algorithm
 // operates, refer to the paper <https://drops.dagstuhl.de/opus/volltexte/2017/7612/pdf/LIPIcs-SEA-2017-24.pdf>.
+#[requires(v.len() > INSERTION_SORT_THRESHOLD)]
+#[ensures(|p| *p < old(v.len()))]
+#[cfg_attr(kani, kani::modifies(v))]
 fn median_of_ninthers<T, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F) -> usize {
     // use `saturating_mul` so the multiplication doesn't overflow on 16-bit platforms.
     let frac = if v.len() <= 1024 {
@@ -245,6 +271,8 @@ fn median_of_ninthers<T, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F)
 /// Moves around the 9 elements at the indices a..i, such that
 /// `v[d]` contains the median of the 9 elements and the other
 /// elements are partitioned around it.
+#[requires([a, b, c, d, e, f, g, h, i].iter().all(|idx| *idx < v.len()))]
+#[cfg_attr(kani, kani::modifies(v))]
 fn ninther<T, F: FnMut(&T, &T) -> bool>(
     v: &mut [T],
     is_less: &mut F,
@@ -289,6 +317,8 @@ fn ninther<T, F: FnMut(&T, &T) -> bool>(
 
 /// returns the index pointing to the median of the 3
 /// elements `v[a]`, `v[b]` and `v[c]`
+#[requires(a < v.len() && b < v.len() && c < v.len())]
+#[ensures(|m| *m == a || *m == b || *m == c)]
 fn median_idx<T, F: FnMut(&T, &T) -> bool>(
     v: &[T],
     is_less: &mut F,
@@ -307,3 +337,104 @@ fn median_idx<T, F: FnMut(&T, &T) -> bool>(
     }
     b
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::slice::sort::shared::is_permutation;
+
+    /// A comparison function that may answer anything, including contradicting itself.
+    fn any_is_less(_: &u32, _: &u32) -> bool {
+        kani::any()
+    }
+
+    #[kani::proof_for_contract(partition_at_index)]
+    #[kani::unwind(8)]
+    fn check_partition_at_index() {
+        const LEN: usize = 6;
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let index = kani::any_where(|index: &usize| *index < LEN);
+        let _ = partition_at_index(&mut v, index, |a: &u32, b: &u32| a < b);
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(8)]
+    fn check_partition_at_index_any_order() {
+        const LEN: usize = 6;
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let index = kani::any_where(|index: &usize| *index < LEN);
+        let _ = partition_at_index(&mut v, index, any_is_less);
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[kani::proof_for_contract(median_idx)]
+    fn check_median_idx() {
+        let v: [u32; 3] = kani::any();
+        let _ = median_idx(&v, &mut any_is_less, kani::any(), kani::any(), kani::any());
+    }
+
+    #[kani::proof_for_contract(ninther)]
+    #[kani::unwind(10)]
+    fn check_ninther() {
+        const LEN: usize = 9;
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        ninther(
+            &mut v,
+            &mut any_is_less,
+            kani::any(),
+            kani::any(),
+            kani::any(),
+            kani::any(),
+            kani::any(),
+            kani::any(),
+            kani::any(),
+            kani::any(),
+            kani::any(),
+        );
+        assert!(is_permutation(&v, &orig));
+    }
+
+    // The two harnesses below use the shortest slices that get past the insertion-sort base
+    // case.
+
+    #[kani::proof_for_contract(median_of_ninthers)]
+    #[kani::unwind(18)]
+    fn check_median_of_ninthers() {
+        const LEN: usize = INSERTION_SORT_THRESHOLD + 1;
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let _ = median_of_ninthers(&mut v, &mut any_is_less);
+        assert!(is_permutation(&v, &orig));
+    }
+
+    // An inconsistent `is_less` drives `median_of_medians` down every path, including the
+    // pivot choices a total order never produces.
+    #[kani::proof_for_contract(median_of_medians)]
+    #[kani::unwind(18)]
+    fn check_median_of_medians() {
+        const LEN: usize = INSERTION_SORT_THRESHOLD + 1;
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let k = kani::any_where(|k: &usize| *k < LEN);
+        median_of_medians(&mut v, &mut any_is_less, k);
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(18)]
+    fn check_median_of_medians_selects() {
+        const LEN: usize = INSERTION_SORT_THRESHOLD + 1;
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let k = kani::any_where(|k: &usize| *k < LEN);
+        median_of_medians(&mut v, &mut |a: &u32, b: &u32| a < b, k);
+        assert!(v[..k].iter().all(|x| *x <= v[k]));
+        assert!(v[k + 1..].iter().all(|x| *x >= v[k]));
+        assert!(is_permutation(&v, &orig));
+    }
+}
@@ -1,6 +1,8 @@
 #![cfg_attr(any(feature = "optimize_for_size", target_pointer_width = "16"), allow(dead_code))]
 
 use crate::marker::Freeze;
+#[cfg(kani)]
+use crate::{intrinsics::raw_eq, mem::MaybeUninit, ptr, slice};
 
 pub(crate) mod pivot;
 pub(crate) mod smallsort;
@@ -12,6 +14,65 @@ pub(crate) trait FreezeMarker {}
 
 impl<T: Freeze> FreezeMarker for T {}
 
+/// Returns `true` if `a` and `b` hold the same elements, counting duplicates.
+///
+/// Used by the sort harnesses to state that sorting only ever moves elements around, even when
+/// `is_less` panics. Kani does not model unwinding, so no harness can look at `v` after such a
+/// panic. Instead they check the two halves of it: comparators that answer arbitrarily must
+/// only ever be handed input values, and the drop guards, dropped on the states a sort stopped
+/// partway through leaves them in, must fill `v` back in with a permutation of the input.
+#[cfg(kani)]
+pub(crate) fn is_permutation<T: PartialEq>(a: &[T], b: &[T]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .all(|x| a.iter().filter(|y| *y == x).count() == b.iter().filter(|y| *y == x).count())
+}
+
+/// Returns `true` if the `len` elements at `a` and at `b` hold the same values, counting
+/// duplicates and comparing them byte for byte.
+///
+/// The sort helpers only see their elements through `is_less`, so this is how their contracts
+/// state that they only ever move elements around.
+///
+/// # Safety
+/// Both ranges must be initialized for `len` elements, and `T` must not contain padding.
+#[cfg(kani)]
+pub(crate) unsafe fn is_raw_permutation<T>(a: *const T, b: *const T, len: usize) -> bool {
+    // SAFETY: guaranteed by the caller.
+    let (a, b) = unsafe { (slice::from_raw_parts(a, len), slice::from_raw_parts(b, len)) };
+    // SAFETY: `raw_eq` only reads initialized elements of a padding-free `T`.
+    let count = |v: &[T], x: &T| v.iter().filter(|y| unsafe { raw_eq(*y, x) }).count();
+    a.iter().all(|x| count(a, x) == count(b, x))
+}
+
+/// The most elements a [`Snapshot`] holds, which covers every length the harnesses sort.
+#[cfg(kani)]
+const SNAPSHOT_CAP: usize = 8;
+
+/// A bytewise copy of up to [`SNAPSHOT_CAP`] elements, which contracts take with `old` to
+/// compare a range that is sorted in place with what it held on entry.
+#[cfg(kani)]
+pub(crate) struct Snapshot<T> {
+    elems: [MaybeUninit<T>; SNAPSHOT_CAP],
+}
+
+#[cfg(kani)]
+impl<T> Snapshot<T> {
+    /// # Safety
+    /// `src` must be valid for `len` reads, and `len` at most [`SNAPSHOT_CAP`].
+    pub(crate) unsafe fn new(src: *const T, len: usize) -> Self {
+        assert!(len <= SNAPSHOT_CAP);
+        let mut elems = [const { MaybeUninit::uninit() }; SNAPSHOT_CAP];
+        // SAFETY: guaranteed by the caller. The copies are never dropped.
+        unsafe { ptr::copy_nonoverlapping(src, elems.as_mut_ptr().cast(), len) };
+        Snapshot { elems }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const T {
+        self.elems.as_ptr().cast()
+    }
+}
+
 /// Finds a run of sorted elements starting at the beginning of the slice.
 ///
 /// Returns the length of the run, and a bool that is false when the run
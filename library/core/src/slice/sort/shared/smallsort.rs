@@ -1,9 +1,16 @@
 //! This module contains a variety of sort implementations that are optimized for small lengths.
 
+use safety::{ensures, requires};
+
 use crate::mem::{self, ManuallyDrop, MaybeUninit};
 use crate::slice::sort::shared::FreezeMarker;
 use crate::{intrinsics, ptr, slice};
 
+#[cfg(kani)]
+use crate::kani;
+#[cfg(kani)]
+use crate::slice::sort::shared::{Snapshot, is_raw_permutation};
+
 // It's important to differentiate between SMALL_SORT_THRESHOLD performance for
 // small slices and small-sort performance sorting small sub-slices as part of
 // the main quicksort loop. For the former, testing showed that the
@@ -383,6 +390,19 @@ where
 /// types. `is_less` could be a huge function and we want to give the compiler an option to
 /// not inline this function. For the same reasons that this function is very perf critical
 /// it should be in the same module as the functions that use it.
+#[requires(crate::ub_checks::can_dereference(v_base.wrapping_add(a_pos))
+    && crate::ub_checks::can_write(v_base.wrapping_add(a_pos)))]
+#[requires(crate::ub_checks::can_dereference(v_base.wrapping_add(b_pos))
+    && crate::ub_checks::can_write(v_base.wrapping_add(b_pos)))]
+#[ensures(|_| unsafe {
+    let (a, b) = (v_base.add(a_pos), v_base.add(b_pos));
+    let old_a = old(Snapshot::new(v_base.add(a_pos), 1));
+    let old_b = old(Snapshot::new(v_base.add(b_pos), 1));
+    let (old_a, old_b) = (&*old_a.as_ptr(), &*old_b.as_ptr());
+    (intrinsics::raw_eq(&*a, old_a) && intrinsics::raw_eq(&*b, old_b))
+        || (intrinsics::raw_eq(&*a, old_b) && intrinsics::raw_eq(&*b, old_a))
+})]
+#[cfg_attr(kani, kani::modifies(v_base.wrapping_add(a_pos), v_base.wrapping_add(b_pos)))]
 unsafe fn swap_if_less<T, F>(v_base: *mut T, a_pos: usize, b_pos: usize, is_less: &mut F)
 where
     F: FnMut(&T, &T) -> bool,
@@ -539,6 +559,18 @@ where
 ///
 /// # Safety
 /// begin < tail and p must be valid and initialized for all begin <= p <= tail.
+#[requires(begin < tail && crate::ub_checks::same_allocation(begin, tail) && {
+    let v = ptr::slice_from_raw_parts_mut(begin, unsafe { tail.offset_from(begin) } as usize + 1);
+    crate::ub_checks::can_dereference(v) && crate::ub_checks::can_write(v)
+})]
+#[ensures(|_| unsafe {
+    let len = tail.offset_from(begin) as usize + 1;
+    let orig = old(Snapshot::new(begin, tail.offset_from(begin) as usize + 1));
+    is_raw_permutation(orig.as_ptr(), begin, len)
+})]
+#[cfg_attr(kani, kani::modifies(
+    ptr::slice_from_raw_parts_mut(begin, unsafe { tail.offset_from(begin) } as usize + 1)
+))]
 unsafe fn insert_tail<T, F: FnMut(&T, &T) -> bool>(begin: *mut T, tail: *mut T, is_less: &mut F) {
     // SAFETY: see individual comments.
     unsafe {
@@ -609,6 +641,11 @@ pub fn insertion_sort_shift_left<T, F: FnMut(&T, &T) -> bool>(
 
 /// SAFETY: The caller MUST guarantee that `v_base` is valid for 4 reads and
 /// `dst` is valid for 4 writes. The result will be stored in `dst[0..4]`.
+#[requires(crate::ub_checks::can_dereference(ptr::slice_from_raw_parts(v_base, 4)))]
+#[requires(crate::ub_checks::can_write(ptr::slice_from_raw_parts_mut(dst, 4)))]
+#[requires(crate::ub_checks::is_nonoverlapping(v_base.cast(), dst.cast(), mem::size_of::<T>(), 4))]
+#[ensures(|_| unsafe { is_raw_permutation(v_base, dst, 4) })]
+#[cfg_attr(kani, kani::modifies(ptr::slice_from_raw_parts_mut(dst, 4)))]
 pub unsafe fn sort4_stable<T, F: FnMut(&T, &T) -> bool>(
     v_base: *const T,
     dst: *mut T,
@@ -665,6 +702,20 @@ pub unsafe fn sort4_stable<T, F: FnMut(&T, &T) -> bool>(
 /// SAFETY: The caller MUST guarantee that `v_base` is valid for 8 reads and
 /// writes, `scratch_base` and `dst` MUST be valid for 8 writes. The result will
 /// be stored in `dst[0..8]`.
+#[requires(crate::ub_checks::can_dereference(ptr::slice_from_raw_parts(v_base, 8))
+    && crate::ub_checks::can_write(ptr::slice_from_raw_parts_mut(v_base, 8)))]
+#[requires(crate::ub_checks::can_write(ptr::slice_from_raw_parts_mut(dst, 8))
+    && crate::ub_checks::can_write(ptr::slice_from_raw_parts_mut(scratch_base, 8)))]
+#[requires(crate::ub_checks::is_nonoverlapping(v_base.cast(), scratch_base.cast(), mem::size_of::<T>(), 8)
+    && crate::ub_checks::is_nonoverlapping(dst.cast(), scratch_base.cast(), mem::size_of::<T>(), 8))]
+#[ensures(|_| unsafe { is_raw_permutation(v_base, dst, 8) })]
+#[cfg_attr(
+    kani,
+    kani::modifies(
+        ptr::slice_from_raw_parts_mut(dst, 8),
+        ptr::slice_from_raw_parts_mut(scratch_base, 8)
+    )
+)]
 unsafe fn sort8_stable<T: FreezeMarker, F: FnMut(&T, &T) -> bool>(
     v_base: *mut T,
     dst: *mut T,
@@ -685,6 +736,20 @@ unsafe fn sort8_stable<T: FreezeMarker, F: FnMut(&T, &T) -> bool>(
 }
 
 #[inline(always)]
+#[requires(crate::ub_checks::can_dereference(left_src) && crate::ub_checks::can_dereference(right_src))]
+#[requires(crate::ub_checks::can_write(dst))]
+#[requires(crate::ub_checks::is_nonoverlapping(left_src.cast(), dst.cast(), mem::size_of::<T>(), 1)
+    && crate::ub_checks::is_nonoverlapping(right_src.cast(), dst.cast(), mem::size_of::<T>(), 1))]
+#[ensures(|&(left, right, out)| unsafe {
+    out == old(dst).add(1)
+        && if left == old(left_src) {
+            right == old(right_src).add(1) && intrinsics::raw_eq(&*old(dst), &*old(right_src))
+        } else {
+            left == old(left_src).add(1) && right == old(right_src)
+                && intrinsics::raw_eq(&*old(dst), &*old(left_src))
+        }
+})]
+#[cfg_attr(kani, kani::modifies(dst))]
 unsafe fn merge_up<T, F: FnMut(&T, &T) -> bool>(
     mut left_src: *const T,
     mut right_src: *const T,
@@ -718,6 +783,21 @@ unsafe fn merge_up<T, F: FnMut(&T, &T) -> bool>(
 }
 
 #[inline(always)]
+#[requires(crate::ub_checks::can_dereference(left_src) && crate::ub_checks::can_dereference(right_src))]
+#[requires(crate::ub_checks::can_write(dst))]
+#[requires(crate::ub_checks::is_nonoverlapping(left_src.cast(), dst.cast(), mem::size_of::<T>(), 1)
+    && crate::ub_checks::is_nonoverlapping(right_src.cast(), dst.cast(), mem::size_of::<T>(), 1))]
+#[ensures(|&(left, right, out)| unsafe {
+    out == old(dst).sub(1)
+        && if left == old(left_src) {
+            right == old(right_src).wrapping_sub(1)
+                && intrinsics::raw_eq(&*old(dst), &*old(right_src))
+        } else {
+            left == old(left_src).wrapping_sub(1) && right == old(right_src)
+                && intrinsics::raw_eq(&*old(dst), &*old(left_src))
+        }
+})]
+#[cfg_attr(kani, kani::modifies(dst))]
 unsafe fn merge_down<T, F: FnMut(&T, &T) -> bool>(
     mut left_src: *const T,
     mut right_src: *const T,
@@ -762,6 +842,10 @@ unsafe fn merge_down<T, F: FnMut(&T, &T) -> bool>(
 ///
 /// Note that T must be Freeze, the comparison function is evaluated on outdated
 /// temporary 'copies' that may not end up in the final array.
+#[requires(v.len() >= 2 && crate::ub_checks::can_write(ptr::slice_from_raw_parts_mut(dst, v.len())))]
+#[requires(crate::ub_checks::is_nonoverlapping(v.as_ptr().cast(), dst.cast(), mem::size_of::<T>(), v.len()))]
+#[ensures(|_| unsafe { is_raw_permutation(v.as_ptr(), dst, v.len()) })]
+#[cfg_attr(kani, kani::modifies(ptr::slice_from_raw_parts_mut(dst, v.len())))]
 unsafe fn bidirectional_merge<T: FreezeMarker, F: FnMut(&T, &T) -> bool>(
     v: &[T],
     dst: *mut T,
@@ -870,3 +954,166 @@ pub(crate) const fn has_efficient_in_place_swap<T>() -> bool {
     // Heuristic that holds true on all tested 64-bit capable architectures.
     mem::size_of::<T>() <= 8 // mem::size_of::<u64>()
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::slice::sort::shared::is_permutation;
+
+    /// A comparison function that may answer anything, including contradicting itself.
+    fn any_is_less(_: &u32, _: &u32) -> bool {
+        kani::any()
+    }
+
+    #[kani::proof_for_contract(swap_if_less)]
+    fn check_swap_if_less() {
+        let orig: [u32; 4] = kani::any();
+        let mut v = orig;
+        let a_pos = kani::any_where(|pos: &usize| *pos < 4);
+        let b_pos = kani::any_where(|pos: &usize| *pos < 4);
+        unsafe { swap_if_less(v.as_mut_ptr(), a_pos, b_pos, &mut any_is_less) };
+        assert!(is_permutation(&orig, &v));
+    }
+
+    #[kani::proof_for_contract(insert_tail)]
+    #[kani::unwind(6)]
+    fn check_insert_tail() {
+        const MAX_LEN: usize = 5;
+        let orig: [u32; MAX_LEN] = kani::any();
+        let mut v = orig;
+        let tail = kani::any_where(|tail: &usize| *tail > 0 && *tail < MAX_LEN);
+        let base = v.as_mut_ptr();
+        unsafe { insert_tail(base, base.add(tail), &mut any_is_less) };
+        assert!(is_permutation(&orig, &v));
+    }
+
+    // The contracts only promise that elements are moved around, as whether the result is sorted
+    // depends on `is_less` being a strict weak order. The `_sorts` harnesses check that for `<`.
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn check_insert_tail_sorts() {
+        const MAX_LEN: usize = 5;
+        let mut v: [u32; MAX_LEN] = kani::any();
+        let tail = kani::any_where(|tail: &usize| *tail > 0 && *tail < MAX_LEN);
+        kani::assume(v[..tail].is_sorted());
+        let orig = v;
+        let base = v.as_mut_ptr();
+        unsafe { insert_tail(base, base.add(tail), &mut |a: &u32, b: &u32| a < b) };
+        assert!(v[..=tail].is_sorted());
+        assert!(is_permutation(&orig, &v));
+    }
+
+    #[kani::proof_for_contract(sort4_stable)]
+    fn check_sort4_stable() {
+        let src: [u32; 4] = kani::any();
+        let mut dst = [MaybeUninit::<u32>::uninit(); 4];
+        unsafe { sort4_stable(src.as_ptr(), dst.as_mut_ptr().cast(), &mut any_is_less) };
+        // Even an inconsistent `is_less` copies every element exactly once.
+        let dst = unsafe { MaybeUninit::array_assume_init(dst) };
+        assert!(is_permutation(&src, &dst));
+    }
+
+    #[kani::proof]
+    fn check_sort4_stable_sorts() {
+        let src: [u32; 4] = kani::any();
+        let mut dst = [MaybeUninit::<u32>::uninit(); 4];
+        unsafe {
+            sort4_stable(src.as_ptr(), dst.as_mut_ptr().cast(), &mut |a: &u32, b: &u32| a < b)
+        };
+        let dst = unsafe { MaybeUninit::array_assume_init(dst) };
+        assert!(dst.is_sorted());
+        assert!(is_permutation(&src, &dst));
+    }
+
+    // An inconsistent `is_less` can make the final merge give up with a panic, but never lets
+    // it read or write out of bounds.
+    #[kani::proof]
+    #[kani::unwind(5)]
+    #[kani::should_panic]
+    fn check_sort8_stable() {
+        let mut v: [u32; 8] = kani::any();
+        let mut dst = [MaybeUninit::<u32>::uninit(); 8];
+        let mut scratch = [MaybeUninit::<u32>::uninit(); 8];
+        unsafe {
+            sort8_stable(
+                v.as_mut_ptr(),
+                dst.as_mut_ptr().cast(),
+                scratch.as_mut_ptr().cast(),
+                &mut any_is_less,
+            )
+        };
+    }
+
+    #[kani::proof_for_contract(sort8_stable)]
+    #[kani::unwind(5)]
+    fn check_sort8_stable_sorts() {
+        let orig: [u32; 8] = kani::any();
+        let mut v = orig;
+        let mut dst = [MaybeUninit::<u32>::uninit(); 8];
+        let mut scratch = [MaybeUninit::<u32>::uninit(); 8];
+        unsafe {
+            sort8_stable(
+                v.as_mut_ptr(),
+                dst.as_mut_ptr().cast(),
+                scratch.as_mut_ptr().cast(),
+                &mut |a: &u32, b: &u32| a < b,
+            )
+        };
+        let dst = unsafe { MaybeUninit::array_assume_init(dst) };
+        assert!(dst.is_sorted());
+        assert!(is_permutation(&orig, &dst));
+    }
+
+    #[kani::proof_for_contract(merge_up)]
+    fn check_merge_up() {
+        let src: [u32; 2] = kani::any();
+        let base = src.as_ptr();
+        let mut dst = MaybeUninit::<u32>::uninit();
+        let (left, right, out) =
+            unsafe { merge_up(base, base.wrapping_add(1), dst.as_mut_ptr(), &mut any_is_less) };
+        // Exactly one of the sources advances, and the destination always does.
+        assert!(
+            (left, right) == (base.wrapping_add(1), base.wrapping_add(1))
+                || (left, right) == (base, base.wrapping_add(2))
+        );
+        assert!(out == dst.as_mut_ptr().wrapping_add(1));
+    }
+
+    #[kani::proof_for_contract(merge_down)]
+    fn check_merge_down() {
+        let src: [u32; 2] = kani::any();
+        let mut dst = MaybeUninit::<u32>::uninit();
+        let base = src.as_ptr();
+        let _ =
+            unsafe { merge_down(base, base.wrapping_add(1), dst.as_mut_ptr(), &mut any_is_less) };
+    }
+
+    // An inconsistent `is_less` leaves the input only partly consumed, which
+    // `bidirectional_merge` detects and panics on before anything reads `dst`.
+    #[kani::proof]
+    #[kani::unwind(5)]
+    #[kani::should_panic]
+    fn check_bidirectional_merge() {
+        const MAX_LEN: usize = 8;
+        let v: [u32; MAX_LEN] = kani::any();
+        let len = kani::any_where(|len: &usize| *len >= 2 && *len <= MAX_LEN);
+        let mut dst = [MaybeUninit::<u32>::uninit(); MAX_LEN];
+        unsafe { bidirectional_merge(&v[..len], dst.as_mut_ptr().cast(), &mut any_is_less) };
+    }
+
+    #[kani::proof_for_contract(bidirectional_merge)]
+    #[kani::unwind(5)]
+    fn check_bidirectional_merge_sorts() {
+        const MAX_LEN: usize = 8;
+        let v: [u32; MAX_LEN] = kani::any();
+        let len = kani::any_where(|len: &usize| *len >= 2 && *len <= MAX_LEN);
+        let v = &v[..len];
+        kani::assume(v[..len / 2].is_sorted() && v[len / 2..].is_sorted());
+        let mut dst = [MaybeUninit::<u32>::uninit(); MAX_LEN];
+        unsafe { bidirectional_merge(v, dst.as_mut_ptr().cast(), &mut |a: &u32, b: &u32| a < b) };
+        let dst = unsafe { MaybeUninit::slice_assume_init_ref(&dst[..len]) };
+        assert!(dst.is_sorted());
+        assert!(is_permutation(v, dst));
+    }
+}
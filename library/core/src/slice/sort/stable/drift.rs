@@ -296,3 +296,71 @@ impl DriftsortRun {
         self.0 >> 1
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::slice::sort::shared::is_permutation;
+    use crate::slice::sort::shared::smallsort::SMALL_SORT_GENERAL_SCRATCH_LEN;
+    use crate::slice::sort::stable::merge::merge_up_stopped;
+
+    const LEN: usize = 4;
+    const SCRATCH_LEN: usize = SMALL_SORT_GENERAL_SCRATCH_LEN;
+
+    // See `is_permutation` for how these harnesses stand in for a panic in `is_less`. `sort`
+    // holds no drop guard of its own; it relies on those of the merges and the small-sorts.
+
+    #[kani::proof]
+    #[kani::unwind(50)]
+    fn check_sort_any_order() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let mut scratch = [MaybeUninit::<u32>::uninit(); SCRATCH_LEN];
+        let v_range = v.as_ptr_range();
+        let scratch_range = scratch.as_ptr_range();
+        // Every element `is_less` is handed sits in `v` or in `scratch`, and is an input value.
+        let mut is_less = |a: &u32, b: &u32| {
+            for x in [a, b] {
+                let x_ptr: *const u32 = x;
+                let in_scratch = scratch_range.contains(&x_ptr.cast::<MaybeUninit<u32>>());
+                assert!((v_range.contains(&x_ptr) || in_scratch) && orig.contains(x));
+            }
+            kani::any()
+        };
+        sort(&mut v, &mut scratch, kani::any(), &mut is_less);
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(50)]
+    fn check_sort_sorts() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let mut scratch = [MaybeUninit::<u32>::uninit(); SCRATCH_LEN];
+        sort(&mut v, &mut scratch, kani::any(), &mut |a: &u32, b: &u32| a < b);
+        assert!(v.is_sorted());
+        assert!(is_permutation(&v, &orig));
+    }
+
+    // Builds the first two runs as `sort` does, then stops partway through the merge that
+    // `logical_merge` does on them and drops its `MergeState`.
+    #[kani::proof]
+    #[kani::unwind(50)]
+    fn check_sort_stopped_in_merge() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let mut scratch = [MaybeUninit::<u32>::uninit(); SCRATCH_LEN];
+        let mut is_less = |_: &u32, _: &u32| kani::any();
+        let left = create_run(&mut v, &mut scratch, kani::any(), true, &mut is_less);
+        let mid = left.len();
+        kani::assume(mid > 0 && mid < LEN);
+        let right = create_run(&mut v[mid..], &mut scratch, kani::any(), true, &mut is_less);
+        let len = mid + right.len();
+        let left_stop = kani::any_where(|stop: &usize| *stop <= mid);
+        let right_stop = kani::any_where(|stop: &usize| *stop <= len - mid);
+        merge_up_stopped(&mut v[..len], &mut scratch, mid, left_stop, right_stop, &mut is_less);
+        assert!(is_permutation(&v, &orig));
+    }
+}
@@ -148,3 +148,89 @@ impl<T> Drop for MergeState<T> {
         }
     }
 }
+
+/// Runs `merge` on `v` with the left run copied to `scratch`, stopping `merge_up` once it has
+/// consumed `left_stop` elements of the left run or `right_stop` of the right one, and then
+/// drops the `MergeState` as a panic in `is_less` at that point would.
+#[cfg(kani)]
+pub(super) fn merge_up_stopped<T, F: FnMut(&T, &T) -> bool>(
+    v: &mut [T],
+    scratch: &mut [MaybeUninit<T>],
+    mid: usize,
+    left_stop: usize,
+    right_stop: usize,
+    is_less: &mut F,
+) {
+    let len = v.len();
+    assert!(mid <= len && mid <= scratch.len());
+    assert!(left_stop <= mid && right_stop <= len - mid);
+    // SAFETY: `buf` holds a copy of the left run, and the runs passed to `merge_up` are
+    // prefixes of the real ones.
+    unsafe {
+        let v_base = v.as_mut_ptr();
+        let v_mid = v_base.add(mid);
+        let buf = MaybeUninit::slice_as_mut_ptr(scratch);
+        ptr::copy_nonoverlapping(v_base, buf, mid);
+        let mut state = MergeState { start: buf, end: buf.add(left_stop), dst: v_base };
+        state.merge_up(v_mid, v_mid.add(right_stop), is_less);
+        state.end = buf.add(mid);
+    }
+}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::slice::sort::shared::is_permutation;
+
+    const LEN: usize = 4;
+
+    // See `is_permutation` for how these harnesses stand in for a panic in `is_less`. They stop
+    // the merge loops early by handing them shorter runs, which leaves `MergeState` as a panic
+    // at that point would.
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_merge_state_fills_gap_up() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let mut buf = [MaybeUninit::<u32>::uninit(); LEN];
+        let mid = kani::any_where(|mid: &usize| *mid > 0 && *mid < LEN);
+        let left_stop = kani::any_where(|stop: &usize| *stop <= mid);
+        let right_stop = kani::any_where(|stop: &usize| *stop <= LEN - mid);
+        let mut is_less = |_: &u32, _: &u32| kani::any();
+        merge_up_stopped(&mut v, &mut buf, mid, left_stop, right_stop, &mut is_less);
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_merge_state_fills_gap_down() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let mut buf = [MaybeUninit::<u32>::uninit(); LEN];
+        let mid = kani::any_where(|mid: &usize| *mid > 0 && *mid < LEN);
+        let left_stop = kani::any_where(|stop: &usize| *stop < mid);
+        let right_stop = kani::any_where(|stop: &usize| *stop < LEN - mid);
+        // SAFETY: `buf` holds a copy of the right run, and `merge_down` stops before it reads
+        // below `left_stop` in `v` or `right_stop` in `buf`.
+        unsafe {
+            let v_base = v.as_mut_ptr();
+            let v_mid = v_base.add(mid);
+            let buf = MaybeUninit::slice_as_mut_ptr(&mut buf);
+            ptr::copy_nonoverlapping(v_mid, buf, LEN - mid);
+            let mut state = MergeState { start: buf, end: buf.add(LEN - mid), dst: v_mid };
+            // A panic in the very first comparison leaves the state as it was set up.
+            if kani::any() {
+                state.merge_down(
+                    v_base.add(left_stop),
+                    buf.add(right_stop),
+                    v_base.add(LEN),
+                    &mut |_: &u32, _: &u32| kani::any(),
+                );
+            }
+        }
+        assert!(is_permutation(&v, &orig));
+    }
+}
@@ -255,3 +255,54 @@ fn has_direct_interior_mutability<T>() -> bool {
     // Otherwise a type like Mutex<Option<Box<str>>> could lead to double free.
     !T::is_freeze()
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::slice::sort::shared::is_permutation;
+
+    const LEN: usize = 6;
+
+    #[kani::proof]
+    #[kani::unwind(8)]
+    fn check_stable_partition_panic_safe() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let mut scratch = [MaybeUninit::<u32>::uninit(); LEN];
+        let pivot_pos = kani::any_where(|pos: &usize| *pos < LEN);
+        let base = v.as_ptr().addr();
+        // See `is_permutation` for why the comparator answers arbitrarily instead of panicking.
+        // `stable_partition` has no drop guard as it only writes `v` after the last comparison,
+        // so every element it hands to `is_less` must still be the one `orig` has there.
+        let mut is_less = |a: &u32, b: &u32| {
+            for x in [a, b] {
+                let i = (x as *const u32).addr().wrapping_sub(base) / mem::size_of::<u32>();
+                assert!(i < LEN && *x == orig[i]);
+            }
+            kani::any()
+        };
+        let num_left = stable_partition(&mut v, &mut scratch, pivot_pos, kani::any(), &mut is_less);
+        assert!(num_left <= LEN);
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(8)]
+    fn check_stable_partition_is_stable() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let mut scratch = [MaybeUninit::<u32>::uninit(); LEN];
+        let pivot_pos = kani::any_where(|pos: &usize| *pos < LEN);
+        let pivot = orig[pivot_pos];
+        let mut is_less = |a: &u32, b: &u32| a < b;
+        let num_left = stable_partition(&mut v, &mut scratch, pivot_pos, false, &mut is_less);
+        assert!(is_permutation(&v, &orig));
+        // Both sides keep the relative order they had in `orig`.
+        let mut left = orig.iter().filter(|x| **x < pivot);
+        let mut right = orig.iter().filter(|x| **x >= pivot);
+        assert!(v[..num_left].iter().all(|x| Some(x) == left.next()));
+        assert!(v[num_left..].iter().all(|x| Some(x) == right.next()));
+    }
+}
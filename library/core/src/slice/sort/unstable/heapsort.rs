@@ -75,3 +75,61 @@ where
         node = child;
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::mem;
+    use crate::slice::sort::shared::is_permutation;
+
+    const LEN: usize = 7;
+
+    // See `is_permutation` for how these harnesses stand in for a panic in `is_less`. There is no
+    // drop guard here: elements are only moved by whole swaps between comparisons, so `v` is a
+    // permutation whenever `is_less` runs.
+
+    /// Returns a comparator checking that it is only handed elements of `v`, which starts at
+    /// `base` and is a permutation of `orig`.
+    fn in_place_is_less(orig: [u32; LEN], base: usize) -> impl FnMut(&u32, &u32) -> bool {
+        move |a: &u32, b: &u32| {
+            for x in [a, b] {
+                let offset = (x as *const u32).addr().wrapping_sub(base);
+                assert!(offset < LEN * mem::size_of::<u32>() && orig.contains(x));
+            }
+            kani::any()
+        }
+    }
+
+    #[kani::proof]
+    #[kani::unwind(8)]
+    fn check_sift_down_panic_safe() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let node = kani::any_where(|node: &usize| *node <= LEN);
+        let mut is_less = in_place_is_less(orig, v.as_ptr().addr());
+        unsafe { sift_down(&mut v, node, &mut is_less) };
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(12)]
+    fn check_heapsort_panic_safe() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let mut is_less = in_place_is_less(orig, v.as_ptr().addr());
+        heapsort(&mut v, &mut is_less);
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(12)]
+    fn check_heapsort_sorts() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        heapsort(&mut v, &mut |a: &u32, b: &u32| a < b);
+        assert!(v.is_sorted());
+        assert!(is_permutation(&v, &orig));
+    }
+}
@@ -389,3 +389,97 @@ impl<T> Drop for GapGuardRaw<T> {
         }
     }
 }
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::kani;
+    use crate::slice::sort::shared::is_permutation;
+
+    const LEN: usize = 5;
+
+    // See `is_permutation` for how these harnesses stand in for a panic in `is_less`. The
+    // `fills_gap` ones drop a gap guard on every state the cyclic permutations keep it in.
+
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_partition_hoare_branchy_cyclic_any_order() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let pivot: u32 = kani::any();
+        let mut is_less = |a: &u32, b: &u32| {
+            assert!(orig.contains(a) && *b == pivot);
+            kani::any()
+        };
+        let num_lt = partition_hoare_branchy_cyclic(&mut v, &pivot, &mut is_less);
+        assert!(num_lt <= LEN);
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[cfg(not(feature = "optimize_for_size"))]
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_partition_lomuto_branchless_cyclic_any_order() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let pivot: u32 = kani::any();
+        let mut is_less = |a: &u32, b: &u32| {
+            assert!(orig.contains(a) && *b == pivot);
+            kani::any()
+        };
+        let num_lt = partition_lomuto_branchless_cyclic(&mut v, &pivot, &mut is_less);
+        assert!(num_lt <= LEN);
+        assert!(is_permutation(&v, &orig));
+    }
+
+    /// Reads an element of `v` out, leaving a gap, and then moves the gap around by copying
+    /// other elements over it, as the cyclic permutations do. Returns the position of the gap
+    /// and the element that was read out.
+    fn any_gap(v: &mut [u32; LEN]) -> (usize, u32) {
+        let mut pos = kani::any_where(|pos: &usize| *pos < LEN);
+        let value = v[pos];
+        for _ in 0..kani::any_where(|moves: &usize| *moves <= LEN) {
+            let next = kani::any_where(|next: &usize| *next < LEN);
+            v[pos] = v[next];
+            pos = next;
+        }
+        (pos, value)
+    }
+
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_gap_guard_fills_gap() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let (pos, value) = any_gap(&mut v);
+        drop(GapGuard { pos: &raw mut v[pos], value: ManuallyDrop::new(value) });
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[cfg(not(feature = "optimize_for_size"))]
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_gap_guard_raw_fills_gap() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let (pos, value) = any_gap(&mut v);
+        let mut value = ManuallyDrop::new(value);
+        drop(GapGuardRaw { pos: &raw mut v[pos], value: &mut *value });
+        assert!(is_permutation(&v, &orig));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn check_partition_splits_at_pivot() {
+        let orig: [u32; LEN] = kani::any();
+        let mut v = orig;
+        let pivot = kani::any_where(|pivot: &usize| *pivot < LEN);
+        let pivot_value = orig[pivot];
+        let num_lt = partition(&mut v, pivot, &mut |a: &u32, b: &u32| a < b);
+        assert!(is_permutation(&v, &orig));
+        assert!(v[num_lt] == pivot_value);
+        assert!(v[..num_lt].iter().all(|x| *x < pivot_value));
+        assert!(v[num_lt + 1..].iter().all(|x| *x >= pivot_value));
+    }
+}
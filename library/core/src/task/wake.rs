@@ -1,5 +1,7 @@
 #![stable(feature = "futures_api", since = "1.36.0")]
 
+use safety::ensures;
+
 use crate::any::Any;
 use crate::marker::PhantomData;
 use crate::mem::{ManuallyDrop, transmute};
@@ -237,6 +239,8 @@ impl<'a> Context<'a> {
     #[rustc_const_stable(feature = "const_waker", since = "1.82.0")]
     #[must_use]
     #[inline]
+    #[ensures(|cx: &Context<'a>| ptr::eq(cx.waker, waker))]
+    #[ensures(|cx: &Context<'a>| ptr::addr_eq(cx.local_waker, waker))]
     pub const fn from_waker(waker: &'a Waker) -> Self {
         ContextBuilder::from_waker(waker).build()
     }
@@ -486,6 +490,8 @@ impl Waker {
     #[inline]
     #[must_use]
     #[stable(feature = "futures_api", since = "1.36.0")]
+    #[ensures(|result| !*result
+        || (self.data() == other.data() && ptr::eq(self.vtable(), other.vtable())))]
     pub fn will_wake(&self, other: &Waker) -> bool {
         // We optimize this by comparing vtable addresses instead of vtable contents.
         // This is permitted since the function is documented as best-effort.
@@ -521,6 +527,7 @@ impl Waker {
     #[must_use]
     #[stable(feature = "waker_getters", since = "1.83.0")]
     #[rustc_const_stable(feature = "waker_getters", since = "1.83.0")]
+    #[ensures(|waker: &Waker| waker.data() == data && ptr::eq(waker.vtable(), vtable))]
     pub const unsafe fn new(data: *const (), vtable: &'static RawWakerVTable) -> Self {
         Waker { waker: RawWaker { data, vtable } }
     }
@@ -540,6 +547,7 @@ impl Waker {
     #[must_use]
     #[stable(feature = "futures_api", since = "1.36.0")]
     #[rustc_const_stable(feature = "const_waker", since = "1.82.0")]
+    #[ensures(|result: &Waker| result.waker == old(RawWaker::new(waker.data, waker.vtable)))]
     pub const unsafe fn from_raw(waker: RawWaker) -> Waker {
         Waker { waker }
     }
@@ -794,6 +802,8 @@ impl LocalWaker {
     #[inline]
     #[must_use]
     #[unstable(feature = "local_waker", issue = "118959")]
+    #[ensures(|result| !*result
+        || (self.data() == other.data() && ptr::eq(self.vtable(), other.vtable())))]
     pub fn will_wake(&self, other: &LocalWaker) -> bool {
         // We optimize this by comparing vtable addresses instead of vtable contents.
         // This is permitted since the function is documented as best-effort.
@@ -822,6 +832,7 @@ impl LocalWaker {
     #[inline]
     #[must_use]
     #[unstable(feature = "local_waker", issue = "118959")]
+    #[ensures(|waker: &LocalWaker| waker.data() == data && ptr::eq(waker.vtable(), vtable))]
     pub const unsafe fn new(data: *const (), vtable: &'static RawWakerVTable) -> Self {
         LocalWaker { waker: RawWaker { data, vtable } }
     }
@@ -835,6 +846,7 @@ impl LocalWaker {
     #[must_use]
     #[unstable(feature = "local_waker", issue = "118959")]
     #[rustc_const_unstable(feature = "local_waker", issue = "118959")]
+    #[ensures(|result: &LocalWaker| result.waker == old(RawWaker::new(waker.data, waker.vtable)))]
     pub const unsafe fn from_raw(waker: RawWaker) -> LocalWaker {
         Self { waker }
     }
@@ -949,3 +961,241 @@ impl fmt::Debug for LocalWaker {
 impl !Send for LocalWaker {}
 #[unstable(feature = "local_waker", issue = "118959")]
 impl !Sync for LocalWaker {}
+
+#[cfg(kani)]
+#[unstable(feature = "kani", issue = "none")]
+mod verify {
+    use super::*;
+    use crate::cell::Cell;
+    use crate::kani;
+    use safety::requires;
+
+    /// Task state behind the data pointer of the mock wakers below. `refs`
+    /// counts the live `RawWaker`s, `wakes` counts wakeups.
+    struct Task {
+        refs: Cell<usize>,
+        wakes: Cell<usize>,
+    }
+
+    impl Task {
+        /// A task that is owned by exactly one waker.
+        fn new() -> Self {
+            Task { refs: Cell::new(1), wakes: Cell::new(0) }
+        }
+
+        fn data(&self) -> *const () {
+            (self as *const Task).cast()
+        }
+    }
+
+    fn is_live(data: *const ()) -> bool {
+        crate::ub_checks::can_dereference(data.cast::<Task>()) && refs(data) > 0
+    }
+
+    fn refs(data: *const ()) -> usize {
+        // SAFETY: only called on data pointers from `Task::data` of a live task.
+        unsafe { &*data.cast::<Task>() }.refs.get()
+    }
+
+    fn wakes(data: *const ()) -> usize {
+        // SAFETY: see `refs`.
+        unsafe { &*data.cast::<Task>() }.wakes.get()
+    }
+
+    // The mock vtable is a reference-counting executor reduced to its
+    // contract: `clone` takes a reference, `wake` and `drop` give one back,
+    // `wake_by_ref` leaves the count alone, and none of them may run once
+    // the count has reached zero.
+    //
+    // That pairing can only be stated on a model. The real `clone`, `wake`,
+    // `wake_by_ref` and `drop` of `Waker` and `LocalWaker` just forward to
+    // the functions handed to `RawWakerVTable::new`, and what those do with
+    // the data pointer is up to the executor; a contract on the real API
+    // could only name the vtable entry it calls, which nothing outside the
+    // vtable can observe. So the counts live in `Task`, and the
+    // `refs_balanced` harnesses check that driving the real wakers keeps
+    // them in step with the wakers that are alive.
+
+    #[requires(is_live(data))]
+    #[ensures(|raw: &RawWaker| raw.data == data && ptr::eq(raw.vtable, &VTABLE))]
+    #[ensures(|_| refs(data) == old(refs(data)) + 1 && wakes(data) == old(wakes(data)))]
+    #[cfg_attr(kani, kani::modifies(data.cast::<Task>()))]
+    unsafe fn mock_clone(data: *const ()) -> RawWaker {
+        let task = unsafe { &*data.cast::<Task>() };
+        task.refs.set(task.refs.get() + 1);
+        RawWaker::new(data, &VTABLE)
+    }
+
+    #[requires(is_live(data))]
+    #[ensures(|_| refs(data) == old(refs(data)) - 1 && wakes(data) == old(wakes(data)) + 1)]
+    #[cfg_attr(kani, kani::modifies(data.cast::<Task>()))]
+    unsafe fn mock_wake(data: *const ()) {
+        let task = unsafe { &*data.cast::<Task>() };
+        task.wakes.set(task.wakes.get() + 1);
+        task.refs.set(task.refs.get() - 1);
+    }
+
+    #[requires(is_live(data))]
+    #[ensures(|_| refs(data) == old(refs(data)) && wakes(data) == old(wakes(data)) + 1)]
+    #[cfg_attr(kani, kani::modifies(data.cast::<Task>()))]
+    unsafe fn mock_wake_by_ref(data: *const ()) {
+        let task = unsafe { &*data.cast::<Task>() };
+        task.wakes.set(task.wakes.get() + 1);
+    }
+
+    #[requires(is_live(data))]
+    #[ensures(|_| refs(data) == old(refs(data)) - 1 && wakes(data) == old(wakes(data)))]
+    #[cfg_attr(kani, kani::modifies(data.cast::<Task>()))]
+    unsafe fn mock_drop(data: *const ()) {
+        let task = unsafe { &*data.cast::<Task>() };
+        task.refs.set(task.refs.get() - 1);
+    }
+
+    static VTABLE: RawWakerVTable =
+        RawWakerVTable::new(mock_clone, mock_wake, mock_wake_by_ref, mock_drop);
+
+    /// Same functions as `VTABLE`, at a different address.
+    static OTHER_VTABLE: RawWakerVTable =
+        RawWakerVTable::new(mock_clone, mock_wake, mock_wake_by_ref, mock_drop);
+
+    fn any_vtable() -> &'static RawWakerVTable {
+        if kani::any() { &VTABLE } else { &OTHER_VTABLE }
+    }
+
+    /// A live task whose counters start from arbitrary values.
+    fn any_task() -> Task {
+        let refs: usize = kani::any_where(|refs| *refs > 0 && *refs < usize::MAX);
+        Task { refs: Cell::new(refs), wakes: Cell::new(kani::any_where(|w| *w < usize::MAX)) }
+    }
+
+    #[kani::proof_for_contract(mock_clone)]
+    fn check_mock_clone() {
+        let task = any_task();
+        let _ = unsafe { mock_clone(task.data()) };
+    }
+
+    #[kani::proof_for_contract(mock_wake)]
+    fn check_mock_wake() {
+        let task = any_task();
+        unsafe { mock_wake(task.data()) };
+    }
+
+    #[kani::proof_for_contract(mock_wake_by_ref)]
+    fn check_mock_wake_by_ref() {
+        let task = any_task();
+        unsafe { mock_wake_by_ref(task.data()) };
+    }
+
+    #[kani::proof_for_contract(mock_drop)]
+    fn check_mock_drop() {
+        let task = any_task();
+        unsafe { mock_drop(task.data()) };
+    }
+
+    macro_rules! check_waker {
+        ($waker:ident, $new:ident, $from_raw:ident, $will_wake:ident, $balanced:ident) => {
+            #[kani::proof_for_contract($waker::new)]
+            fn $new() {
+                let data = kani::any::<usize>() as *const ();
+                let _ = ManuallyDrop::new(unsafe { $waker::new(data, any_vtable()) });
+            }
+
+            #[kani::proof_for_contract($waker::from_raw)]
+            fn $from_raw() {
+                let data = kani::any::<usize>() as *const ();
+                let raw = RawWaker::new(data, any_vtable());
+                let _ = ManuallyDrop::new(unsafe { $waker::from_raw(raw) });
+            }
+
+            #[kani::proof_for_contract($waker::will_wake)]
+            fn $will_wake() {
+                let (a, b) = (kani::any::<usize>() as *const (), kani::any::<usize>() as *const ());
+                let a = ManuallyDrop::new(unsafe { $waker::new(a, any_vtable()) });
+                let b = ManuallyDrop::new(unsafe { $waker::new(b, any_vtable()) });
+                let _ = a.will_wake(&b);
+            }
+
+            /// Runs an arbitrary sequence of clones, wakes, comparisons and
+            /// drops over a few waker slots. After every step the task holds
+            /// exactly one reference per live waker, and once every waker is
+            /// gone no reference is left.
+            #[kani::proof]
+            #[kani::unwind(5)]
+            fn $balanced() {
+                const SLOTS: usize = 3;
+                let task = Task::new();
+                let mut wakers: [Option<$waker>; SLOTS] =
+                    [Some(unsafe { $waker::new(task.data(), &VTABLE) }), None, None];
+                let mut expected_wakes = 0;
+
+                for _ in 0..4 {
+                    let i: usize = kani::any_where(|i| *i < SLOTS);
+                    let j: usize = kani::any_where(|j| *j < SLOTS);
+                    match kani::any::<u8>() % 6 {
+                        0 => {
+                            let waker = wakers[i].clone();
+                            if waker.is_some() {
+                                wakers[j] = waker;
+                            }
+                        }
+                        1 => {
+                            if let Some(waker) = wakers[i].take() {
+                                waker.wake();
+                                expected_wakes += 1;
+                            }
+                        }
+                        2 => {
+                            if let Some(waker) = &wakers[i] {
+                                waker.wake_by_ref();
+                                expected_wakes += 1;
+                            }
+                        }
+                        3 => {
+                            if let (Some(a), Some(b)) = (&wakers[i], &wakers[j]) {
+                                assert!(a.will_wake(b));
+                            }
+                        }
+                        4 => {
+                            if let [Some(a), Some(b), _] = &mut wakers {
+                                a.clone_from(b);
+                            }
+                        }
+                        _ => wakers[i] = None,
+                    }
+                    assert_eq!(task.refs.get(), wakers.iter().filter(|w| w.is_some()).count());
+                    assert_eq!(task.wakes.get(), expected_wakes);
+                }
+
+                drop(wakers);
+                assert_eq!(task.refs.get(), 0);
+            }
+        };
+    }
+
+    check_waker!(
+        Waker,
+        check_waker_new,
+        check_waker_from_raw,
+        check_waker_will_wake,
+        check_waker_refs_balanced
+    );
+    check_waker!(
+        LocalWaker,
+        check_local_waker_new,
+        check_local_waker_from_raw,
+        check_local_waker_will_wake,
+        check_local_waker_refs_balanced
+    );
+
+    #[kani::proof_for_contract(Context::from_waker)]
+    fn check_context_from_waker() {
+        let task = Task::new();
+        let waker = unsafe { Waker::new(task.data(), &VTABLE) };
+        let cx = Context::from_waker(&waker);
+        cx.waker().wake_by_ref();
+        cx.local_waker().wake_by_ref();
+        assert_eq!(task.wakes.get(), 2);
+        drop(waker);
+        assert_eq!(task.refs.get(), 0);
+    }
+}
@@ -270,9 +270,18 @@ trivial_invariant!(isize);
 
 trivial_invariant!(());
 trivial_invariant!(bool);
-trivial_invariant!(char);
 
 trivial_invariant!(f16);
 trivial_invariant!(f32);
 trivial_invariant!(f64);
 trivial_invariant!(f128);
+
+impl Invariant for char {
+    /// A `char` must be a Unicode scalar value, i.e., a code point in `0..=0x10FFFF`
+    /// that is not a surrogate.
+    #[inline(always)]
+    fn is_safe(&self) -> bool {
+        let c = *self as u32;
+        !(0xD800..=0xDFFF).contains(&c) && c <= 0x10FFFF
+    }
+}